@@ -1,10 +1,49 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, Bytes, BytesN, Env, IntoVal,
-    InvokeError, Symbol, Val, Vec, vec,
+    contract, contracterror, contractevent, contractimpl, contracttype, token, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, InvokeError, String, Symbol, Val, Vec, vec,
 };
 
+mod provenance {
+    use soroban_sdk::{contractclient, contracttype, Address, BytesN, Env, String};
+
+    pub use super::{HashAlgorithm, JobResult};
+
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct MerkleCommitment {
+        pub root: BytesN<32>,
+        pub chunk_size: u32,
+        pub chunk_count: u32,
+    }
+
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ContentLayout {
+        Whole,
+        Chunked(MerkleCommitment),
+    }
+
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct CertificateDetails {
+        pub storage_id: String,
+        pub manifest_hash: String,
+        pub attestation_hash: String,
+        pub hash_algorithm: HashAlgorithm,
+        pub layout: ContentLayout,
+        pub job: JobResult,
+        pub job_digest: BytesN<32>,
+    }
+
+    #[allow(dead_code)]
+    #[contractclient(name = "Client")]
+    pub trait ProvenanceClient {
+        fn mint(env: &Env, minter: Address, to: Address, details: CertificateDetails) -> u64;
+    }
+}
+
 #[contracttype]
 pub enum DataKey {
     Registry,
@@ -15,10 +54,77 @@ pub enum DataKey {
     NextRequestId,
     /// Temporary-storage key for a specific verification request.
     Request(u64),
+    /// Ledgers a new request stays open before it expires.
+    RequestTtl,
+    /// Fee escrowed from the requester on submission.
+    RequestFee,
+    /// Persistent-storage key for the compact record of a finalized request.
+    Archived(u64),
+    /// Persistent-storage key for the pending-queue head: every request id at
+    /// or below it is settled, so `next_pending` starts scanning after it.
+    PendingHead,
+    /// Registered providers, in registration order.
+    Providers,
+    /// Selection weight (stake or reputation) of a provider. Kept in instance
+    /// storage so a committee draw reads every weight from one entry.
+    ProviderWeight(Address),
+    /// Number of providers assigned to each request.
+    CommitteeSize,
+    /// Temporary-storage key for the commit–reveal round of a committee request.
+    Round(u64),
+    /// Temporary-storage key for the callback registered with a request.
+    Callback(u64),
+    /// Persistent-storage key mapping a content digest and job digest (see
+    /// `Job::digest`) to its pending or certified request.
+    ContentIndex(BytesN<32>, BytesN<32>),
 }
 
-/// Fixed TTL (in ledgers) for verification requests stored in temporary storage.
-const REQUEST_TTL_LEDGERS: u32 = 100;
+/// Default TTL (in ledgers) for verification requests stored in temporary storage.
+const DEFAULT_REQUEST_TTL_LEDGERS: u32 = 100;
+
+/// Longest request TTL (~30 days at 5s per ledger) the admin may configure.
+const MAX_REQUEST_TTL_LEDGERS: u32 = 518_400;
+
+/// Ledgers (~180 days at 5s per ledger) an archived request is kept alive for
+/// each time it is written or read.
+const ARCHIVE_TTL_LEDGERS: u32 = 3_110_400;
+
+/// Remaining TTL (~30 days) below which an archived request is extended.
+const ARCHIVE_TTL_THRESHOLD: u32 = 518_400;
+
+/// Selection weight of a newly added provider.
+const DEFAULT_PROVIDER_WEIGHT: u32 = 100;
+
+/// Most providers the oracle registers, bounding the instance entry that
+/// holds their weights.
+const MAX_PROVIDERS: u32 = 64;
+
+/// Largest committee the admin may configure.
+const MAX_COMMITTEE_SIZE: u32 = 7;
+
+/// Registry eligibility checks a committee draw makes at most.
+const MAX_ELIGIBILITY_CHECKS: u32 = 2 * MAX_COMMITTEE_SIZE;
+
+/// Ledgers (~100 seconds at 5s per ledger) committee members have to commit
+/// to a result, capped at half the request TTL.
+const COMMIT_WINDOW_LEDGERS: u32 = 20;
+
+/// Ledgers a request stays readable past its deadline so that it can still be
+/// settled: its round finalized, or its fee refunded.
+const SETTLEMENT_LEDGERS: u32 = 720;
+
+/// Ledgers (~5 minutes at 5s per ledger) a provider's claim on a request lasts.
+const LEASE_LEDGERS: u32 = 60;
+
+/// Most entries `next_pending` returns per call.
+const MAX_PENDING_PAGE: u32 = 50;
+
+/// Most request ids `next_pending` inspects per call, keeping its footprint
+/// within the per-transaction ledger entry limit.
+const MAX_PENDING_SCAN: u64 = 64;
+
+/// Most settled ids the pending-queue head moves past per submission.
+const PENDING_HEAD_STEPS: u32 = 8;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -26,14 +132,436 @@ pub enum RequestState {
     Pending,
     Verified,
     Rejected,
+    /// The deadline passed before the request was fulfilled.
+    Expired,
+    /// The requester withdrew the request before it was fulfilled.
+    Cancelled,
+}
+
+/// Algorithm that produced a content digest, identified by its multihash code.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum HashAlgorithm {
+    Sha256 = 0x12,
+    Keccak256 = 0x1b,
+    Blake3 = 0x1e,
+    Blake2b256 = 0xb220,
+}
+
+/// Content digest tagged with the algorithm that produced it, so verifiers
+/// can recompute it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContentHash {
+    pub algorithm: HashAlgorithm,
+    pub digest: BytesN<32>,
+}
+
+/// Kind of verification a request asks for.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum JobType {
+    ContentIntegrity = 0,
+    ManifestSignature = 1,
+    C2paValidation = 2,
+    AiGenerationDetection = 3,
+}
+
+/// Parameters of a manifest signature check.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ManifestSignatureParams {
+    pub manifest_uri: String,
+    /// Certificate subject the manifest must be signed by, if any.
+    pub expected_signer: Option<String>,
+}
+
+/// Parameters of a C2PA manifest store validation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct C2paParams {
+    /// Location of the asset carrying the manifest store.
+    pub asset_uri: String,
+}
+
+/// Parameters of an AI-generation check.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AiDetectionParams {
+    /// Score, in basis points, at or above which the content is rejected as
+    /// AI-generated.
+    pub threshold_bps: u32,
+}
+
+/// Verification job carried by a request, with its parameters.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Job {
+    /// Check that the content hashes to the request's `content_hash`.
+    ContentIntegrity,
+    ManifestSignature(ManifestSignatureParams),
+    C2paValidation(C2paParams),
+    AiGenerationDetection(AiDetectionParams),
+}
+
+impl Job {
+    pub fn job_type(&self) -> JobType {
+        match self {
+            Job::ContentIntegrity => JobType::ContentIntegrity,
+            Job::ManifestSignature(_) => JobType::ManifestSignature,
+            Job::C2paValidation(_) => JobType::C2paValidation,
+            Job::AiGenerationDetection(_) => JobType::AiGenerationDetection,
+        }
+    }
+
+    /// SHA-256 of the XDR-encoded job, identifying it together with its
+    /// parameters. `ContentIntegrity` takes no parameters and digests to all
+    /// zeroes, the value other certifiers of content integrity use.
+    pub fn digest(&self, env: &Env) -> BytesN<32> {
+        match self {
+            Job::ContentIntegrity => BytesN::from_array(env, &[0; 32]),
+            _ => env.crypto().sha256(&self.clone().to_xdr(env)).into(),
+        }
+    }
+
+    /// Whether `result` allows content to be verified under this job's
+    /// parameters: the manifest must be signed by the expected signer, if
+    /// any, and the AI-generation score must stay below the threshold.
+    fn allows_verified(&self, result: &JobResult) -> bool {
+        match (self, result) {
+            (Job::ManifestSignature(params), JobResult::ManifestSignature(found)) => params
+                .expected_signer
+                .as_ref()
+                .is_none_or(|signer| *signer == found.signer),
+            (Job::AiGenerationDetection(params), JobResult::AiGenerationDetection(found)) => {
+                found.score_bps < params.threshold_bps
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Findings of a manifest signature check.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ManifestSignatureResult {
+    /// Subject of the certificate that signed the manifest.
+    pub signer: String,
+}
+
+/// Findings of a C2PA manifest store validation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct C2paResult {
+    /// Claim generator recorded in the active manifest.
+    pub claim_generator: String,
+    /// SHA-256 of the active manifest.
+    pub active_manifest: BytesN<32>,
+}
+
+/// Findings of an AI-generation detector.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AiDetectionResult {
+    /// Likelihood that the content is AI-generated, in basis points.
+    pub score_bps: u32,
+    /// Detector model that produced the score.
+    pub model: String,
+}
+
+/// Job-specific findings a provider attests to. Verified results are
+/// recorded on the provenance certificate.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JobResult {
+    ContentIntegrity,
+    ManifestSignature(ManifestSignatureResult),
+    C2paValidation(C2paResult),
+    AiGenerationDetection(AiDetectionResult),
+}
+
+impl JobResult {
+    pub fn job_type(&self) -> JobType {
+        match self {
+            JobResult::ContentIntegrity => JobType::ContentIntegrity,
+            JobResult::ManifestSignature(_) => JobType::ManifestSignature,
+            JobResult::C2paValidation(_) => JobType::C2paValidation,
+            JobResult::AiGenerationDetection(_) => JobType::AiGenerationDetection,
+        }
+    }
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct VerificationRequest {
     pub id: u64,
-    pub content_hash: BytesN<32>,
+    /// Account that submitted the request and paid its fee.
+    pub requester: Address,
+    pub content_hash: ContentHash,
+    pub job: Job,
+    pub state: RequestState,
+    /// Last ledger sequence at which the request can be fulfilled.
+    pub deadline: u32,
+    /// Token the escrowed fee was paid in, if any.
+    pub fee_token: Option<Address>,
+    /// Fee held in escrow.
+    pub fee: i128,
+    /// Provider holding the work lease, if any.
+    pub leased_to: Option<Address>,
+    /// Last ledger sequence covered by the lease.
+    pub lease_expires: u32,
+    /// Providers drawn to work on the request; only they may fulfill it.
+    pub assigned: Vec<Address>,
+    /// Last ledger sequence at which only `assigned` may claim a request
+    /// with a single assignee; afterwards, unless the request is under an
+    /// active lease, any eligible provider may claim it and take over.
+    pub assigned_until: u32,
+    /// Last ledger sequence at which committee members may commit.
+    pub commit_ends: u32,
+}
+
+/// Contract function a requester wants called when its request is finalized.
+///
+/// The function is invoked as `function(request_id: u64, state: RequestState,
+/// certificate_id: Option<u64>)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Callback {
+    pub contract: Address,
+    pub function: Symbol,
+}
+
+/// Outcome of notifying a request's callback.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CallbackStatus {
+    /// The requester registered no callback.
+    NotRequested,
+    Succeeded,
+    /// The callback failed or does not exist; finalization went ahead anyway.
+    Failed,
+}
+
+/// A committee member's hidden result.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResultCommitment {
+    pub provider: Address,
+    /// SHA-256 of the signed fulfillment's XDR followed by a 32-byte salt.
+    pub hash: BytesN<32>,
+}
+
+/// A committee member's revealed result.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevealedResult {
+    pub provider: Address,
+    pub verified: bool,
+    pub result: JobResult,
+    /// SHA-256 of the signed fulfillment.
+    pub attestation_hash: BytesN<32>,
+}
+
+/// Commit–reveal state of a request assigned to more than one provider.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Round {
+    pub commitments: Vec<ResultCommitment>,
+    pub reveals: Vec<RevealedResult>,
+}
+
+/// Outstanding work returned by `next_pending`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingRequest {
+    pub id: u64,
+    pub content_hash: ContentHash,
+    pub job: Job,
+    pub assigned: Vec<Address>,
+}
+
+/// Page of outstanding work returned by `next_pending`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingPage {
+    pub requests: Vec<PendingRequest>,
+    /// Cursor for the next call: the last request id inspected.
+    pub cursor: u64,
+}
+
+/// Request already covering some content for a job, returned by
+/// `find_request`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExistingRequest {
+    pub request_id: u64,
+    /// Certificate minted for the request; `None` while it is pending.
+    pub certificate_id: Option<u64>,
+}
+
+/// Compact audit record of a request that reached a final state.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedRequest {
+    pub id: u64,
+    pub content_hash: ContentHash,
     pub state: RequestState,
+    /// Provider that fulfilled the request, if any.
+    pub provider: Option<Address>,
+    /// SHA-256 of the signed fulfillment, if any.
+    pub attestation_hash: Option<BytesN<32>>,
+    /// Provenance certificate minted for a verified request, if the mint succeeded.
+    pub certificate_id: Option<u64>,
+    pub callback_status: CallbackStatus,
+    /// Ledger sequence at which the request was finalized.
+    pub finalized_at: u32,
+}
+
+/// A request as returned by `get_request`, from whichever tier holds it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum RequestRecord {
+    /// Still pending in temporary storage.
+    Live(VerificationRequest),
+    /// Finalized and archived in persistent storage.
+    Archived(ArchivedRequest),
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestSubmitted {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+    pub hash_algorithm: HashAlgorithm,
+    pub job_type: JobType,
+    pub requester: Address,
+    pub deadline: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestClaimed {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+    pub provider: Address,
+    pub lease_expires: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestVerified {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+    pub provider: Address,
+    pub certificate_id: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestRejected {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+    pub provider: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestCancelled {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestExpired {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+}
+
+/// Fee charged per request, paid to the fulfilling provider.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestFee {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Result a provider's TEE signs for a request.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Fulfillment {
+    pub request_id: u64,
+    /// Whether the content passed verification.
+    pub verified: bool,
+    /// Findings for the request's job; must match its job type and, if
+    /// `verified`, satisfy its parameters.
+    pub result: JobResult,
+    /// Ed25519 key of the TEE that produced the result.
+    pub signer: BytesN<32>,
+    pub tee_hash: BytesN<32>,
+}
+
+/// One provider result inside a fulfillment batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchLeaf {
+    pub request_id: u64,
+    /// Whether the content passed verification.
+    pub verified: bool,
+    pub result: JobResult,
+}
+
+/// Results for many requests, signed once by a provider's TEE.
+///
+/// `root` is the root of a binary SHA-256 Merkle tree over `leaf_count`
+/// leaves `sha256(0x00 || xdr(BatchLeaf))`, with inner nodes
+/// `sha256(0x01 || left || right)`; a node without a sibling is promoted to
+/// the next level unchanged.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FulfillmentBatch {
+    pub root: BytesN<32>,
+    pub leaf_count: u32,
+    /// Ed25519 key of the TEE that produced the results.
+    pub signer: BytesN<32>,
+    pub tee_hash: BytesN<32>,
+}
+
+/// A batch leaf with its Merkle inclusion proof, listing sibling hashes
+/// from the leaf level upwards.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchProof {
+    pub index: u32,
+    pub leaf: BatchLeaf,
+    pub siblings: Vec<BytesN<32>>,
+}
+
+/// Outcome of one proof submitted to `fulfill_batch`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BatchOutcome {
+    /// The request was finalized in the given state.
+    Finalized(RequestState),
+    /// The leaf is not included under the signed root.
+    InvalidProof,
+    /// The request could not be fulfilled; carries the `Error` code.
+    Refused(u32),
 }
 
 #[contracterror]
@@ -43,6 +571,48 @@ pub enum Error {
     NotInitialized = 1,
     UnauthorizedSigner = 2,
     AlreadyInitialized = 3,
+    /// No request was ever submitted under this id.
+    RequestNotFound = 4,
+    /// The request outlived its deadline and was dropped from storage.
+    RequestExpired = 5,
+    /// The request already reached a final state.
+    AlreadyFinalized = 6,
+    InvalidTtl = 7,
+    /// The caller is not a registered oracle provider.
+    UnauthorizedProvider = 8,
+    InvalidFee = 9,
+    /// Another provider holds an active lease on the request.
+    RequestLeased = 11,
+    /// The provider was not assigned to the request.
+    NotAssigned = 12,
+    InvalidCommitteeSize = 13,
+    /// Committee requests are settled through `commit_result` and `reveal_result`.
+    CommitRevealRequired = 14,
+    CommitWindowClosed = 15,
+    /// Results cannot be revealed until the commit window closes.
+    CommitWindowOpen = 16,
+    AlreadyCommitted = 17,
+    AlreadyRevealed = 18,
+    /// The revealed result does not match the provider's commitment.
+    CommitmentMismatch = 19,
+    /// The round can only be finalized after the deadline or once every
+    /// committee member revealed, and its request can no longer be cancelled
+    /// once a member committed.
+    RoundInProgress = 20,
+    /// The result is for a different job type than the request asks for.
+    JobMismatch = 21,
+    /// Another committee member already committed the same hash.
+    DuplicateCommitment = 22,
+    /// No registered provider is eligible for work in the registry.
+    NoEligibleProviders = 23,
+    /// The content already has a pending or certified request for this job;
+    /// see `find_request`.
+    AlreadyRequested = 24,
+    /// The result is marked verified but fails the job's parameters.
+    ResultContradictsJob = 25,
+    /// The request can still be fulfilled.
+    RequestNotExpired = 26,
+    TooManyProviders = 27,
 }
 
 #[contracterror]
@@ -75,7 +645,10 @@ impl Contract {
         Ok(())
     }
 
-    pub fn add_provider(env: Env, provider: Address) {
+    /// Register a provider with the default selection weight.
+    ///
+    /// Fails with `TooManyProviders` once `MAX_PROVIDERS` are registered.
+    pub fn add_provider(env: Env, provider: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
@@ -83,8 +656,22 @@ impl Contract {
             .expect("Admin not set; initialize first");
         admin.require_auth();
 
-        let key = DataKey::Provider(provider);
+        let key = DataKey::Provider(provider.clone());
+        if env.storage().persistent().has(&key) {
+            return Ok(());
+        }
+        let mut providers = Self::get_providers(env.clone());
+        if providers.len() >= MAX_PROVIDERS {
+            return Err(Error::TooManyProviders);
+        }
         env.storage().persistent().set(&key, &true);
+        env.storage().instance().set(
+            &DataKey::ProviderWeight(provider.clone()),
+            &DEFAULT_PROVIDER_WEIGHT,
+        );
+        providers.push_back(provider);
+        env.storage().instance().set(&DataKey::Providers, &providers);
+        Ok(())
     }
 
     pub fn remove_provider(env: Env, provider: Address) {
@@ -95,8 +682,79 @@ impl Contract {
             .expect("Admin not set; initialize first");
         admin.require_auth();
 
-        let key = DataKey::Provider(provider);
+        let key = DataKey::Provider(provider.clone());
         env.storage().persistent().remove(&key);
+        env.storage()
+            .instance()
+            .remove(&DataKey::ProviderWeight(provider.clone()));
+        let mut providers = Self::get_providers(env.clone());
+        if let Some(index) = providers.first_index_of(&provider) {
+            providers.remove(index);
+            env.storage().instance().set(&DataKey::Providers, &providers);
+        }
+    }
+
+    /// Return the registered providers, in registration order.
+    pub fn get_providers(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Providers)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Set a provider's selection weight, e.g. from its stake or reputation.
+    /// Only the admin may call this. Providers with zero weight are never
+    /// assigned new requests.
+    pub fn set_provider_weight(env: Env, provider: Address, weight: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        if !Self::is_provider(env.clone(), provider.clone()) {
+            return Err(Error::UnauthorizedProvider);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ProviderWeight(provider), &weight);
+        Ok(())
+    }
+
+    /// Return a provider's selection weight, or 0 for unknown providers.
+    pub fn get_provider_weight(env: Env, provider: Address) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ProviderWeight(provider))
+            .unwrap_or(0)
+    }
+
+    /// Set how many providers are drawn for each new request.
+    /// Only the admin may call this.
+    ///
+    /// Returns `InvalidCommitteeSize` for zero or anything above `MAX_COMMITTEE_SIZE`.
+    pub fn set_committee_size(env: Env, size: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        if size == 0 || size > MAX_COMMITTEE_SIZE {
+            return Err(Error::InvalidCommitteeSize);
+        }
+        env.storage().instance().set(&DataKey::CommitteeSize, &size);
+        Ok(())
+    }
+
+    /// Return how many providers are drawn for each new request.
+    pub fn get_committee_size(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CommitteeSize)
+            .unwrap_or(1)
     }
 
     pub fn is_provider(env: Env, provider: Address) -> bool {
@@ -115,68 +773,640 @@ impl Contract {
         payload: Bytes,
         signature: BytesN<64>,
     ) -> Result<(), Error> {
-        let registry: Address = env
+        Self::check_attestation(&env, &provider, &tee_hash, &payload, &signature)
+    }
+
+    /// Set the number of ledgers new requests stay open. Only the admin may call this.
+    ///
+    /// Returns `InvalidTtl` for zero or anything above `MAX_REQUEST_TTL_LEDGERS`.
+    pub fn set_request_ttl(env: Env, ledgers: u32) -> Result<(), Error> {
+        let admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::Registry)
+            .get(&DataKey::Admin)
             .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
 
-        // Verify provider and hash are authorized in the Registry
-        let is_verified: bool = env.invoke_contract(
-            &registry,
-            &soroban_sdk::Symbol::new(&env, "is_verified"),
-            soroban_sdk::vec![
-                &env,
-                tee_hash.into_val(&env),
-                provider.clone().into_val(&env)
-            ],
-        );
-
-        if !is_verified {
-            return Err(Error::UnauthorizedSigner);
+        if ledgers == 0 || ledgers > MAX_REQUEST_TTL_LEDGERS {
+            return Err(Error::InvalidTtl);
         }
-
-        // Verify the cryptographic signature (aborts entirely if invalid)
-        env.crypto().ed25519_verify(&provider, &payload, &signature);
-
+        env.storage().instance().set(&DataKey::RequestTtl, &ledgers);
         Ok(())
     }
 
-
-
-    /// Submit a new content verification request to the Oracle.
-    ///
-    /// Generates a unique request ID, stores the request in temporary storage
-    /// with `Pending` state, and returns the request ID to the caller.
-    pub fn submit_request(env: Env, content_hash: BytesN<32>) -> u64 {
-        // 1. Load and increment the monotonically increasing request counter.
-        let mut next_id: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::NextRequestId)
-            .unwrap_or(0);
-        next_id += 1;
+    /// Return the number of ledgers new requests stay open.
+    pub fn get_request_ttl(env: Env) -> u32 {
         env.storage()
             .instance()
-            .set(&DataKey::NextRequestId, &next_id);
+            .get(&DataKey::RequestTtl)
+            .unwrap_or(DEFAULT_REQUEST_TTL_LEDGERS)
+    }
 
-        // 2. Build the verification request in Pending state.
+    /// Set the fee escrowed from requesters on submission. Only the admin may
+    /// call this. A zero amount disables the fee.
+    pub fn set_request_fee(env: Env, token: Address, amount: i128) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        if amount < 0 {
+            return Err(Error::InvalidFee);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RequestFee, &RequestFee { token, amount });
+        Ok(())
+    }
+
+    /// Return the configured request fee, if any.
+    pub fn get_request_fee(env: Env) -> Option<RequestFee> {
+        env.storage().instance().get(&DataKey::RequestFee)
+    }
+
+    /// Submit a new content verification request to the Oracle.
+    ///
+    /// Generates a unique request ID, stores the request in temporary storage
+    /// with `Pending` state, and returns the request ID to the caller. The
+    /// request can be fulfilled until `get_request_ttl` ledgers from now.
+    /// `requester` must authorize the call and pays the configured fee into
+    /// escrow. A committee of `get_committee_size` providers is drawn from
+    /// the ledger PRNG among those the registry reports as eligible (see
+    /// `is_eligible_operator`), weighted by provider weight; only they may
+    /// claim or fulfill the request. Fails with `NoEligibleProviders` if
+    /// nobody can be drawn. A single assignee that has not claimed the
+    /// request within 60 ledgers loses it to whichever eligible provider
+    /// claims it next; see `claim_request`.
+    ///
+    /// If `callback` is set, it is invoked once the request is finalized so
+    /// the requester does not have to poll; see `Callback`.
+    ///
+    /// `job` selects the kind of verification providers perform; their
+    /// attested results must be of the same type.
+    ///
+    /// Content that already has a pending request, or a verified request
+    /// with a certificate, for the same job and parameters is not queued
+    /// again and fails with `AlreadyRequested`; `find_request` returns the
+    /// request and certificate covering it. This deliberately departs from
+    /// returning the existing id on resubmission: the existing request may
+    /// belong to a different requester, who alone can cancel it and whose
+    /// callback it runs, so handing it out as the caller's own would be
+    /// misleading.
+    pub fn submit_request(
+        env: Env,
+        requester: Address,
+        content_hash: ContentHash,
+        job: Job,
+        callback: Option<Callback>,
+    ) -> Result<u64, Error> {
+        requester.require_auth();
+
+        if Self::existing_request(&env, &content_hash.digest, &job).is_some() {
+            return Err(Error::AlreadyRequested);
+        }
+
+        // 1. Move the pending-queue head past requests settled since.
+        Self::advance_pending_head(&env);
+
+        // 2. Load and increment the monotonically increasing request counter.
+        let mut next_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRequestId)
+            .unwrap_or(0);
+        next_id += 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRequestId, &next_id);
+
+        // 3. Escrow the request fee.
+        let (fee_token, fee) = match Self::get_request_fee(env.clone()) {
+            Some(fee) if fee.amount > 0 => {
+                token::Client::new(&env, &fee.token).transfer(
+                    &requester,
+                    env.current_contract_address(),
+                    &fee.amount,
+                );
+                (Some(fee.token), fee.amount)
+            }
+            _ => (None, 0),
+        };
+
+        // 4. Build the verification request in Pending state.
+        let ttl = Self::get_request_ttl(env.clone());
+        let assigned = Self::draw_committee(&env)?;
+        let live_ledgers = ttl.saturating_add(SETTLEMENT_LEDGERS);
+        let deadline = env.ledger().sequence().saturating_add(ttl);
         let request = VerificationRequest {
             id: next_id,
-            content_hash,
+            requester: requester.clone(),
+            content_hash: content_hash.clone(),
+            job: job.clone(),
             state: RequestState::Pending,
+            deadline,
+            fee_token,
+            fee,
+            leased_to: None,
+            lease_expires: 0,
+            assigned,
+            assigned_until: deadline.min(env.ledger().sequence() + LEASE_LEDGERS),
+            commit_ends: env.ledger().sequence() + COMMIT_WINDOW_LEDGERS.min(ttl / 2),
         };
 
-        // 3. Store the request in temporary storage keyed by its ID and set TTL.
+        // 5. Store the request in temporary storage keyed by its ID and set TTL.
         let key = DataKey::Request(next_id);
         env.storage().temporary().set(&key, &request);
-        // Ensure the request lives for a fixed TTL window.
+        // Keep the request readable past its deadline until it can no longer
+        // be settled.
         env.storage()
             .temporary()
-            .extend_ttl(&key, 0, REQUEST_TTL_LEDGERS);
+            .extend_ttl(&key, live_ledgers, live_ledgers);
+        let key = DataKey::ContentIndex(content_hash.digest.clone(), job.digest(&env));
+        env.storage().persistent().set(&key, &next_id);
+        if let Some(callback) = callback {
+            let key = DataKey::Callback(next_id);
+            env.storage().temporary().set(&key, &callback);
+            env.storage()
+                .temporary()
+                .extend_ttl(&key, live_ledgers, live_ledgers);
+        }
+        RequestSubmitted {
+            request_id: next_id,
+            content_hash: content_hash.digest,
+            hash_algorithm: content_hash.algorithm,
+            job_type: job.job_type(),
+            requester,
+            deadline: request.deadline,
+        }
+        .publish(&env);
+
+        // 6. Return the unique request ID to the caller.
+        Ok(next_id)
+    }
+
+    /// Take a lease on a pending request before working on it.
+    ///
+    /// `provider` must be a registered oracle provider and authorize the call.
+    /// While the lease lasts (60 ledgers, capped at the request deadline)
+    /// only `provider` may fulfill the request and `next_pending` hides it;
+    /// once it lapses the request returns to the queue. The holder may claim
+    /// again to renew. Returns the last ledger covered by the lease.
+    ///
+    /// Until `assigned_until` only the assigned provider may claim. After
+    /// that, a request nobody holds a lease on may be claimed by any
+    /// provider the registry reports as eligible, which replaces the
+    /// assignee; a silent assignee therefore does not hold up the request
+    /// until it expires.
+    ///
+    /// Committee requests are not leased; they fail with
+    /// `CommitRevealRequired` and are settled through `commit_result`.
+    pub fn claim_request(env: Env, provider: Address, request_id: u64) -> Result<u32, Error> {
+        provider.require_auth();
+        if !Self::is_provider(env.clone(), provider.clone()) {
+            return Err(Error::UnauthorizedProvider);
+        }
 
-        // 4. Return the unique request ID to the caller.
-        next_id
+        let mut request = Self::load_request(&env, request_id)?;
+        let sequence = env.ledger().sequence();
+        if sequence > request.deadline {
+            return Err(Error::RequestExpired);
+        }
+        if request.assigned.len() > 1 {
+            return Err(Error::CommitRevealRequired);
+        }
+        if Self::leased_to_other(&env, &request, &provider) {
+            return Err(Error::RequestLeased);
+        }
+        if !Self::is_assigned(&request, &provider) {
+            if sequence <= request.assigned_until || !Self::is_eligible(&env, &provider)? {
+                return Err(Error::NotAssigned);
+            }
+            request.assigned = vec![&env, provider.clone()];
+        }
+
+        request.leased_to = Some(provider.clone());
+        request.lease_expires = request.deadline.min(sequence + LEASE_LEDGERS);
+        env.storage()
+            .temporary()
+            .set(&DataKey::Request(request_id), &request);
+
+        RequestClaimed {
+            request_id,
+            content_hash: request.content_hash.digest,
+            provider,
+            lease_expires: request.lease_expires,
+        }
+        .publish(&env);
+        Ok(request.lease_expires)
+    }
+
+    /// Record a provider's signed result for a pending request.
+    ///
+    /// `provider` must be a registered oracle provider and authorize the call,
+    /// and must hold the lease if the request is currently claimed.
+    /// The fulfillment is signed by a TEE key the registry trusts for
+    /// `tee_hash` and lists as one of `provider`'s keys; the signature covers
+    /// the XDR encoding of `fulfillment`.
+    /// The escrowed fee is paid to `provider`. A request past its deadline is
+    /// moved to `Expired` instead and its fee refunded to the requester.
+    pub fn fulfill_request(
+        env: Env,
+        provider: Address,
+        fulfillment: Fulfillment,
+        signature: BytesN<64>,
+    ) -> Result<RequestState, Error> {
+        provider.require_auth();
+        if !Self::is_provider(env.clone(), provider.clone()) {
+            return Err(Error::UnauthorizedProvider);
+        }
+
+        let mut request = Self::load_request(&env, fulfillment.request_id)?;
+        if !Self::admit_fulfillment(
+            &env,
+            &mut request,
+            &provider,
+            fulfillment.verified,
+            &fulfillment.result,
+        )? {
+            return Ok(request.state);
+        }
+
+        let payload = fulfillment.clone().to_xdr(&env);
+        Self::check_operator_key(&env, &provider, &fulfillment.signer)?;
+        Self::check_attestation(
+            &env,
+            &fulfillment.signer,
+            &fulfillment.tee_hash,
+            &payload,
+            &signature,
+        )?;
+
+        let attestation_hash = env.crypto().sha256(&payload).into();
+        Ok(Self::settle(
+            &env,
+            request,
+            provider,
+            fulfillment.verified,
+            fulfillment.result,
+            attestation_hash,
+        ))
+    }
+
+    /// Record many results signed by one TEE signature over a Merkle root.
+    ///
+    /// The signature covers the XDR encoding of `batch` and is checked once,
+    /// as for `fulfill_request`. Each proof whose leaf is included under
+    /// `batch.root` is then applied like an individual fulfillment by
+    /// `provider`. Proofs that do not check out, or whose request cannot be
+    /// fulfilled, are skipped without affecting the rest of the batch.
+    /// Returns one outcome per proof, in order. Archived requests record the
+    /// SHA-256 of the signed batch as their attestation hash.
+    pub fn fulfill_batch(
+        env: Env,
+        provider: Address,
+        batch: FulfillmentBatch,
+        signature: BytesN<64>,
+        proofs: Vec<BatchProof>,
+    ) -> Result<Vec<BatchOutcome>, Error> {
+        provider.require_auth();
+        if !Self::is_provider(env.clone(), provider.clone()) {
+            return Err(Error::UnauthorizedProvider);
+        }
+
+        let payload = batch.clone().to_xdr(&env);
+        Self::check_operator_key(&env, &provider, &batch.signer)?;
+        Self::check_attestation(&env, &batch.signer, &batch.tee_hash, &payload, &signature)?;
+        let attestation_hash: BytesN<32> = env.crypto().sha256(&payload).into();
+
+        let mut outcomes = Vec::new(&env);
+        for proof in proofs.iter() {
+            let outcome = if !Self::verify_batch_proof(&env, &batch, &proof) {
+                BatchOutcome::InvalidProof
+            } else {
+                match Self::fulfill_batch_leaf(&env, &provider, &proof.leaf, &attestation_hash) {
+                    Ok(state) => BatchOutcome::Finalized(state),
+                    Err(error) => BatchOutcome::Refused(error as u32),
+                }
+            };
+            outcomes.push_back(outcome);
+        }
+        Ok(outcomes)
+    }
+
+    /// Commit to a result for a committee request without revealing it.
+    ///
+    /// Requests assigned to more than one provider are settled in two
+    /// phases so that members cannot copy each other. Until `commit_ends`,
+    /// each assigned provider commits
+    /// `sha256(provider_xdr || fulfillment_xdr || salt)`, where `fulfillment`
+    /// is the TEE-signed result it will later reveal. Binding the provider
+    /// into the hash keeps a member from re-submitting another's commitment;
+    /// a hash already in the round fails with `DuplicateCommitment`.
+    pub fn commit_result(
+        env: Env,
+        provider: Address,
+        request_id: u64,
+        commitment: BytesN<32>,
+    ) -> Result<(), Error> {
+        provider.require_auth();
+        if !Self::is_provider(env.clone(), provider.clone()) {
+            return Err(Error::UnauthorizedProvider);
+        }
+
+        let request = Self::load_request(&env, request_id)?;
+        if env.ledger().sequence() > request.commit_ends {
+            return Err(Error::CommitWindowClosed);
+        }
+        if !Self::is_assigned(&request, &provider) {
+            return Err(Error::NotAssigned);
+        }
+
+        let mut round = Self::get_round(env.clone(), request_id);
+        if round.commitments.iter().any(|c| c.provider == provider) {
+            return Err(Error::AlreadyCommitted);
+        }
+        if round.commitments.iter().any(|c| c.hash == commitment) {
+            return Err(Error::DuplicateCommitment);
+        }
+        round.commitments.push_back(ResultCommitment {
+            provider,
+            hash: commitment,
+        });
+        Self::save_round(&env, &request, &round);
+        Ok(())
+    }
+
+    /// Reveal a committed result once the commit window has closed.
+    ///
+    /// `provider`, `fulfillment` and `salt` must hash to the provider's
+    /// commitment, and
+    /// `signature` must be a valid TEE signature over `fulfillment` as for
+    /// `fulfill_request`. Only revealed results count toward the outcome.
+    pub fn reveal_result(
+        env: Env,
+        provider: Address,
+        fulfillment: Fulfillment,
+        salt: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        provider.require_auth();
+
+        let request = Self::load_request(&env, fulfillment.request_id)?;
+        let sequence = env.ledger().sequence();
+        if sequence <= request.commit_ends {
+            return Err(Error::CommitWindowOpen);
+        }
+        if sequence > request.deadline {
+            return Err(Error::RequestExpired);
+        }
+
+        let mut round = Self::get_round(env.clone(), request.id);
+        if round.reveals.iter().any(|r| r.provider == provider) {
+            return Err(Error::AlreadyRevealed);
+        }
+        let payload = fulfillment.clone().to_xdr(&env);
+        let mut preimage = provider.clone().to_xdr(&env);
+        preimage.append(&payload);
+        preimage.append(&salt.into());
+        let expected: BytesN<32> = env.crypto().sha256(&preimage).into();
+        if !round
+            .commitments
+            .iter()
+            .any(|c| c.provider == provider && c.hash == expected)
+        {
+            return Err(Error::CommitmentMismatch);
+        }
+        Self::check_result(&request, fulfillment.verified, &fulfillment.result)?;
+        Self::check_operator_key(&env, &provider, &fulfillment.signer)?;
+        Self::check_attestation(
+            &env,
+            &fulfillment.signer,
+            &fulfillment.tee_hash,
+            &payload,
+            &signature,
+        )?;
+
+        round.reveals.push_back(RevealedResult {
+            provider,
+            verified: fulfillment.verified,
+            result: fulfillment.result,
+            attestation_hash: env.crypto().sha256(&payload).into(),
+        });
+        Self::save_round(&env, &request, &round);
+        Ok(())
+    }
+
+    /// Settle a committee request from its revealed results.
+    ///
+    /// Callable by anyone once every assigned provider has revealed, or
+    /// after the deadline while the request is still held for settlement.
+    /// The majority of revealed results decides between `Verified` and
+    /// `Rejected` (ties reject), and the first majority revealer's findings
+    /// go on the certificate; without any reveal the
+    /// request expires. The fee is split evenly among providers in the
+    /// majority, with any remainder refunded. Assigned providers that never
+    /// revealed, whether or not they committed, have their selection weight
+    /// halved.
+    pub fn finalize_round(env: Env, request_id: u64) -> Result<RequestState, Error> {
+        let mut request = Self::load_request(&env, request_id)?;
+        let sequence = env.ledger().sequence();
+        let round = Self::get_round(env.clone(), request_id);
+        let all_revealed =
+            !request.assigned.is_empty() && round.reveals.len() == request.assigned.len();
+        if sequence <= request.deadline && !(sequence > request.commit_ends && all_revealed) {
+            return Err(Error::RoundInProgress);
+        }
+
+        for member in request.assigned.iter() {
+            if !round.reveals.iter().any(|r| r.provider == member) {
+                let weight = Self::get_provider_weight(env.clone(), member.clone());
+                if weight > 0 {
+                    env.storage()
+                        .instance()
+                        .set(&DataKey::ProviderWeight(member), &(weight / 2));
+                }
+            }
+        }
+
+        let verified_votes = round.reveals.iter().filter(|r| r.verified).count() as u32;
+        let verified = verified_votes * 2 > round.reveals.len();
+        let mut majority = Vec::new(&env);
+        for reveal in round.reveals.iter() {
+            if reveal.verified == verified {
+                majority.push_back(reveal);
+            }
+        }
+        env.storage()
+            .temporary()
+            .remove(&DataKey::Round(request_id));
+
+        let Some(first) = majority.first() else {
+            request.state = RequestState::Expired;
+            Self::release_fee(&env, &request, &request.requester);
+            Self::finish(&env, &request, None, None, None);
+            return Ok(request.state);
+        };
+
+        request.state = if verified {
+            RequestState::Verified
+        } else {
+            RequestState::Rejected
+        };
+        if let Some(fee_token) = &request.fee_token {
+            let token = token::Client::new(&env, fee_token);
+            let share = request.fee / i128::from(majority.len());
+            for reveal in majority.iter() {
+                token.transfer(&env.current_contract_address(), &reveal.provider, &share);
+            }
+            let remainder = request.fee - share * i128::from(majority.len());
+            if remainder > 0 {
+                token.transfer(
+                    &env.current_contract_address(),
+                    &request.requester,
+                    &remainder,
+                );
+            }
+        }
+        Self::finish(
+            &env,
+            &request,
+            Some(first.provider),
+            Some(first.attestation_hash),
+            Some(first.result),
+        );
+        Ok(request.state)
+    }
+
+    /// Return the commit–reveal state of a request; empty if nobody committed yet.
+    pub fn get_round(env: Env, request_id: u64) -> Round {
+        env.storage()
+            .temporary()
+            .get(&DataKey::Round(request_id))
+            .unwrap_or_else(|| Round {
+                commitments: Vec::new(&env),
+                reveals: Vec::new(&env),
+            })
+    }
+
+    /// Return the request covering `content_hash` for `job`: one still
+    /// pending, or one that was verified and certified, together with its
+    /// certificate id. This is the request a submission failing with
+    /// `AlreadyRequested` collided with; once it is cancelled or goes stale
+    /// the content can be submitted again.
+    pub fn find_request(env: Env, content_hash: ContentHash, job: Job) -> Option<ExistingRequest> {
+        Self::existing_request(&env, &content_hash.digest, &job)
+    }
+
+    /// Withdraw a pending request and refund its escrowed fee.
+    ///
+    /// Only the requester may cancel, and only before the request is
+    /// fulfilled or its deadline passes; past the deadline the fee is
+    /// recovered with `claim_refund`. Later fulfillments of the id fail
+    /// with `AlreadyFinalized`.
+    ///
+    /// A committee request cannot be cancelled once any member committed,
+    /// failing with `RoundInProgress`: revealed results are public, and
+    /// cancelling would let the requester back out of an unwelcome outcome
+    /// for a full refund.
+    pub fn cancel_request(env: Env, request_id: u64) -> Result<(), Error> {
+        let mut request = Self::load_request(&env, request_id)?;
+        request.requester.require_auth();
+        if env.ledger().sequence() > request.deadline {
+            return Err(Error::AlreadyFinalized);
+        }
+        if !Self::get_round(env.clone(), request_id).commitments.is_empty() {
+            return Err(Error::RoundInProgress);
+        }
+
+        request.state = RequestState::Cancelled;
+        Self::release_fee(&env, &request, &request.requester);
+        Self::finish(&env, &request, None, None, None);
+        Ok(())
+    }
+
+    /// Refund the escrowed fee of a request that passed its deadline
+    /// unfulfilled, moving it to `Expired`.
+    ///
+    /// Only the requester may claim, within `SETTLEMENT_LEDGERS` of the
+    /// deadline while the request is still held. Fails with
+    /// `RequestNotExpired` before the deadline; committee requests are
+    /// settled through `finalize_round` instead.
+    pub fn claim_refund(env: Env, request_id: u64) -> Result<(), Error> {
+        let mut request = Self::load_request(&env, request_id)?;
+        request.requester.require_auth();
+        if request.assigned.len() > 1 {
+            return Err(Error::CommitRevealRequired);
+        }
+        if env.ledger().sequence() <= request.deadline {
+            return Err(Error::RequestNotExpired);
+        }
+
+        Self::expire(&env, &mut request);
+        Ok(())
+    }
+
+    /// Return a request from temporary storage or, once finalized, its
+    /// archived record. A pending request past its deadline is reported as
+    /// `Expired`.
+    ///
+    /// Fails with `RequestExpired` for ids that were issued but lapsed
+    /// without being finalized, and with `RequestNotFound` for ids never issued.
+    pub fn get_request(env: Env, request_id: u64) -> Result<RequestRecord, Error> {
+        let archived_key = DataKey::Archived(request_id);
+        if let Some(archived) = env.storage().persistent().get(&archived_key) {
+            env.storage().persistent().extend_ttl(
+                &archived_key,
+                ARCHIVE_TTL_THRESHOLD,
+                ARCHIVE_TTL_LEDGERS,
+            );
+            return Ok(RequestRecord::Archived(archived));
+        }
+
+        let mut request = Self::load_request(&env, request_id)?;
+        if env.ledger().sequence() > request.deadline {
+            request.state = RequestState::Expired;
+        }
+        Ok(RequestRecord::Live(request))
+    }
+
+    /// List requests still awaiting fulfillment, oldest first.
+    ///
+    /// Returns up to `limit` (at most 50) requests with an id greater than
+    /// `cursor`, inspecting at most `MAX_PENDING_SCAN` ids per call; pass the
+    /// returned cursor to the next call to page through the queue until it
+    /// reaches the last issued id. Requests past their deadline or under an
+    /// active lease are skipped.
+    pub fn next_pending(env: Env, cursor: u64, limit: u32) -> PendingPage {
+        let sequence = env.ledger().sequence();
+        let limit = limit.min(MAX_PENDING_PAGE);
+        let last_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRequestId)
+            .unwrap_or(0);
+        let mut id = cursor.max(Self::pending_head(&env));
+        let end = last_id.min(id.saturating_add(MAX_PENDING_SCAN));
+        let mut requests = Vec::new(&env);
+        while id < end && requests.len() < limit {
+            id += 1;
+            if let Some(request) = Self::pending_request(&env, id) {
+                if request.leased_to.is_some() && request.lease_expires >= sequence {
+                    continue;
+                }
+                requests.push_back(PendingRequest {
+                    id: request.id,
+                    content_hash: request.content_hash,
+                    job: request.job,
+                    assigned: request.assigned,
+                });
+            }
+        }
+        PendingPage {
+            requests,
+            cursor: id,
+        }
     }
 
     /// Verify a TEE measurement hash via the external Registry contract.
@@ -215,6 +1445,551 @@ impl Contract {
             Ok(Err(_)) | Err(_) => Err(OracleError::RegistryCallFailed),
         }
     }
+
+    /// Check that `provider` and `tee_hash` are trusted by the registry and
+    /// that `signature` over `payload` is valid.
+    /// Aborts (panics) if the Ed25519 signature is invalid.
+    fn check_attestation(
+        env: &Env,
+        provider: &BytesN<32>,
+        tee_hash: &BytesN<32>,
+        payload: &Bytes,
+        signature: &BytesN<64>,
+    ) -> Result<(), Error> {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Registry)
+            .ok_or(Error::NotInitialized)?;
+
+        // Verify provider and hash are authorized in the Registry
+        let is_verified: bool = env.invoke_contract(
+            &registry,
+            &soroban_sdk::Symbol::new(env, "is_verified"),
+            soroban_sdk::vec![
+                env,
+                tee_hash.into_val(env),
+                provider.into_val(env)
+            ],
+        );
+
+        if !is_verified {
+            return Err(Error::UnauthorizedSigner);
+        }
+
+        // Verify the cryptographic signature (aborts entirely if invalid)
+        env.crypto().ed25519_verify(provider, payload, signature);
+
+        Ok(())
+    }
+
+    /// Check that the registry lists `signer` as a key of `provider`, so
+    /// assigned providers can only attest with their own TEE keys.
+    fn check_operator_key(env: &Env, provider: &Address, signer: &BytesN<32>) -> Result<(), Error> {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Registry)
+            .ok_or(Error::NotInitialized)?;
+        let owned: bool = env.invoke_contract(
+            &registry,
+            &Symbol::new(env, "is_operator_key"),
+            vec![env, provider.into_val(env), signer.into_val(env)],
+        );
+        if !owned {
+            return Err(Error::UnauthorizedSigner);
+        }
+        Ok(())
+    }
+
+    /// Pay a request's escrowed fee out to `to`.
+    fn release_fee(env: &Env, request: &VerificationRequest, to: &Address) {
+        if let Some(fee_token) = &request.fee_token {
+            token::Client::new(env, fee_token).transfer(
+                &env.current_contract_address(),
+                to,
+                &request.fee,
+            );
+        }
+    }
+
+    /// Check that `provider` may fulfill `request` now with `result`. A
+    /// request past its deadline is expired and refunded instead, returning
+    /// `Ok(false)`.
+    fn admit_fulfillment(
+        env: &Env,
+        request: &mut VerificationRequest,
+        provider: &Address,
+        verified: bool,
+        result: &JobResult,
+    ) -> Result<bool, Error> {
+        if request.assigned.len() > 1 {
+            return Err(Error::CommitRevealRequired);
+        }
+        if env.ledger().sequence() > request.deadline {
+            Self::expire(env, request);
+            return Ok(false);
+        }
+        if Self::leased_to_other(env, request, provider) {
+            return Err(Error::RequestLeased);
+        }
+        if !Self::is_assigned(request, provider) {
+            return Err(Error::NotAssigned);
+        }
+        Self::check_result(request, verified, result)?;
+        Ok(true)
+    }
+
+    /// Move a request past its deadline to `Expired` and refund its fee.
+    fn expire(env: &Env, request: &mut VerificationRequest) {
+        request.state = RequestState::Expired;
+        Self::release_fee(env, request, &request.requester);
+        Self::finish(env, request, None, None, None);
+    }
+
+    /// Check that `result` answers `request`'s job and, if it is marked
+    /// `verified`, satisfies the job's parameters.
+    fn check_result(
+        request: &VerificationRequest,
+        verified: bool,
+        result: &JobResult,
+    ) -> Result<(), Error> {
+        if result.job_type() != request.job.job_type() {
+            return Err(Error::JobMismatch);
+        }
+        if verified && !request.job.allows_verified(result) {
+            return Err(Error::ResultContradictsJob);
+        }
+        Ok(())
+    }
+
+    /// Finalize an admitted request with `provider`'s attested result and
+    /// pay it the escrowed fee.
+    fn settle(
+        env: &Env,
+        mut request: VerificationRequest,
+        provider: Address,
+        verified: bool,
+        result: JobResult,
+        attestation_hash: BytesN<32>,
+    ) -> RequestState {
+        request.state = if verified {
+            RequestState::Verified
+        } else {
+            RequestState::Rejected
+        };
+        Self::release_fee(env, &request, &provider);
+        Self::finish(
+            env,
+            &request,
+            Some(provider),
+            Some(attestation_hash),
+            Some(result),
+        );
+        request.state
+    }
+
+    fn fulfill_batch_leaf(
+        env: &Env,
+        provider: &Address,
+        leaf: &BatchLeaf,
+        attestation_hash: &BytesN<32>,
+    ) -> Result<RequestState, Error> {
+        let mut request = Self::load_request(env, leaf.request_id)?;
+        if !Self::admit_fulfillment(env, &mut request, provider, leaf.verified, &leaf.result)? {
+            return Ok(request.state);
+        }
+        Ok(Self::settle(
+            env,
+            request,
+            provider.clone(),
+            leaf.verified,
+            leaf.result.clone(),
+            attestation_hash.clone(),
+        ))
+    }
+
+    /// Check one leaf's Merkle inclusion proof against `batch.root`.
+    fn verify_batch_proof(env: &Env, batch: &FulfillmentBatch, proof: &BatchProof) -> bool {
+        if proof.index >= batch.leaf_count {
+            return false;
+        }
+
+        let mut leaf = Bytes::from_array(env, &[0x00]);
+        leaf.append(&proof.leaf.clone().to_xdr(env));
+        let mut hash: BytesN<32> = env.crypto().sha256(&leaf).into();
+
+        let mut siblings = proof.siblings.iter();
+        let mut index = proof.index;
+        let mut width = batch.leaf_count;
+        while width > 1 {
+            let (left, right) = if index % 2 == 1 {
+                match siblings.next() {
+                    Some(sibling) => (sibling, hash),
+                    None => return false,
+                }
+            } else if index + 1 < width {
+                match siblings.next() {
+                    Some(sibling) => (hash, sibling),
+                    None => return false,
+                }
+            } else {
+                // Last node of an odd-width level is promoted unchanged.
+                index /= 2;
+                width = width.div_ceil(2);
+                continue;
+            };
+            let mut node = Bytes::from_array(env, &[0x01]);
+            node.append(&left.into());
+            node.append(&right.into());
+            hash = env.crypto().sha256(&node).into();
+            index /= 2;
+            width = width.div_ceil(2);
+        }
+        siblings.next().is_none() && hash == batch.root
+    }
+
+    /// Archive a request that reached a final state and announce the outcome.
+    /// `provider`, `attestation_hash` and `result` are required for
+    /// `Verified` and `Rejected` requests. Verified requests get a provenance
+    /// certificate owned by the requester, and the requester's callback is
+    /// notified.
+    fn finish(
+        env: &Env,
+        request: &VerificationRequest,
+        provider: Option<Address>,
+        attestation_hash: Option<BytesN<32>>,
+        result: Option<JobResult>,
+    ) {
+        let certificate_id = match (&request.state, &attestation_hash, result) {
+            (RequestState::Verified, Some(attestation_hash), Some(result)) => {
+                Self::mint_certificate(env, request, attestation_hash, result)
+            }
+            _ => None,
+        };
+        let callback_status = Self::notify_callback(env, request, certificate_id);
+        Self::update_content_index(env, request, certificate_id);
+        Self::archive(
+            env,
+            request,
+            provider.clone(),
+            attestation_hash,
+            certificate_id,
+            callback_status,
+        );
+
+        let (request_id, content_hash) = (request.id, request.content_hash.digest.clone());
+        match (&request.state, provider) {
+            (RequestState::Verified, Some(provider)) => RequestVerified {
+                request_id,
+                content_hash,
+                provider,
+                certificate_id,
+            }
+            .publish(env),
+            (RequestState::Rejected, Some(provider)) => RequestRejected {
+                request_id,
+                content_hash,
+                provider,
+            }
+            .publish(env),
+            (RequestState::Cancelled, _) => RequestCancelled {
+                request_id,
+                content_hash,
+            }
+            .publish(env),
+            (RequestState::Expired, _) => RequestExpired {
+                request_id,
+                content_hash,
+            }
+            .publish(env),
+            _ => {}
+        }
+    }
+
+    /// Return the request already covering `digest` for `job`: one still
+    /// pending, or one that was verified and certified. Stale index entries
+    /// are dropped.
+    fn existing_request(env: &Env, digest: &BytesN<32>, job: &Job) -> Option<ExistingRequest> {
+        let key = DataKey::ContentIndex(digest.clone(), job.digest(env));
+        let request_id: u64 = env.storage().persistent().get(&key)?;
+        let archived: Option<ArchivedRequest> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Archived(request_id));
+        let (current, certificate_id) = match archived {
+            Some(record) => (record.certificate_id.is_some(), record.certificate_id),
+            None => (
+                env.storage()
+                    .temporary()
+                    .get::<_, VerificationRequest>(&DataKey::Request(request_id))
+                    .is_some_and(|request| env.ledger().sequence() <= request.deadline),
+                None,
+            ),
+        };
+        if !current {
+            env.storage().persistent().remove(&key);
+            return None;
+        }
+        Some(ExistingRequest {
+            request_id,
+            certificate_id,
+        })
+    }
+
+    /// Keep a finalized request in the content index only if it produced a
+    /// certificate, so that any other outcome can be resubmitted. Provenance
+    /// certifies each manifest once per job, so a second request could
+    /// not mint.
+    fn update_content_index(env: &Env, request: &VerificationRequest, certificate_id: Option<u64>) {
+        let key =
+            DataKey::ContentIndex(request.content_hash.digest.clone(), request.job.digest(env));
+        if env.storage().persistent().get::<_, u64>(&key) != Some(request.id) {
+            // A newer request took over after this one went stale.
+            return;
+        }
+        if certificate_id.is_some() {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, ARCHIVE_TTL_THRESHOLD, ARCHIVE_TTL_LEDGERS);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    /// Mint a provenance certificate for a verified request. Returns `None`
+    /// if the oracle has no provenance contract or the mint fails, e.g.
+    /// because the provenance admin has not authorized the oracle as a minter.
+    fn mint_certificate(
+        env: &Env,
+        request: &VerificationRequest,
+        attestation_hash: &BytesN<32>,
+        result: JobResult,
+    ) -> Option<u64> {
+        let provenance: Address = env.storage().instance().get(&DataKey::Provenance)?;
+        let details = provenance::CertificateDetails {
+            // Oracle requests carry no storage location for the content.
+            storage_id: String::from_str(env, ""),
+            manifest_hash: Self::hex_string(env, &request.content_hash.digest.to_array()),
+            attestation_hash: Self::hex_string(env, &attestation_hash.to_array()),
+            hash_algorithm: request.content_hash.algorithm,
+            layout: provenance::ContentLayout::Whole,
+            job: result,
+            job_digest: request.job.digest(env),
+        };
+        let client = provenance::Client::new(env, &provenance);
+        let minter = env.current_contract_address();
+        match client.try_mint(&minter, &request.requester, &details) {
+            Ok(Ok(certificate_id)) => Some(certificate_id),
+            _ => None,
+        }
+    }
+
+    /// Invoke the request's callback, if any. Failures are recorded rather
+    /// than propagated so that a broken consumer cannot block finalization.
+    fn notify_callback(
+        env: &Env,
+        request: &VerificationRequest,
+        certificate_id: Option<u64>,
+    ) -> CallbackStatus {
+        let key = DataKey::Callback(request.id);
+        let Some(callback) = env.storage().temporary().get::<_, Callback>(&key) else {
+            return CallbackStatus::NotRequested;
+        };
+        env.storage().temporary().remove(&key);
+        let args: Vec<Val> = vec![
+            env,
+            request.id.into_val(env),
+            request.state.clone().into_val(env),
+            certificate_id.into_val(env),
+        ];
+        match env.try_invoke_contract::<Val, InvokeError>(
+            &callback.contract,
+            &callback.function,
+            args,
+        ) {
+            Ok(Ok(_)) => CallbackStatus::Succeeded,
+            _ => CallbackStatus::Failed,
+        }
+    }
+
+    /// Lower-case hex encoding of a 32-byte digest.
+    fn hex_string(env: &Env, bytes: &[u8; 32]) -> String {
+        const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+        let mut result = [0u8; 64];
+        for (i, byte) in bytes.iter().enumerate() {
+            result[i * 2] = HEX_CHARS[(byte >> 4) as usize];
+            result[i * 2 + 1] = HEX_CHARS[(byte & 0x0f) as usize];
+        }
+        String::from_bytes(env, &result)
+    }
+
+    /// Store a round alongside its request, so it lives as long as the request.
+    fn save_round(env: &Env, request: &VerificationRequest, round: &Round) {
+        let key = DataKey::Round(request.id);
+        env.storage().temporary().set(&key, round);
+        let ttl = request
+            .deadline
+            .saturating_add(SETTLEMENT_LEDGERS)
+            .saturating_sub(env.ledger().sequence());
+        env.storage().temporary().extend_ttl(&key, ttl, ttl);
+    }
+
+    /// Replace a finalized request's temporary entry with its archived record.
+    fn archive(
+        env: &Env,
+        request: &VerificationRequest,
+        provider: Option<Address>,
+        attestation_hash: Option<BytesN<32>>,
+        certificate_id: Option<u64>,
+        callback_status: CallbackStatus,
+    ) {
+        let key = DataKey::Archived(request.id);
+        let record = ArchivedRequest {
+            id: request.id,
+            content_hash: request.content_hash.clone(),
+            state: request.state.clone(),
+            provider,
+            attestation_hash,
+            certificate_id,
+            callback_status,
+            finalized_at: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&key, &record);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ARCHIVE_TTL_THRESHOLD, ARCHIVE_TTL_LEDGERS);
+        env.storage()
+            .temporary()
+            .remove(&DataKey::Request(request.id));
+    }
+
+    /// Draw up to `get_committee_size` distinct providers, each with
+    /// probability proportional to its weight among those not yet drawn.
+    /// Only drawn providers are checked with the registry: one it does not
+    /// report as eligible is skipped and another drawn in its place, for at
+    /// most `MAX_ELIGIBILITY_CHECKS` checks.
+    fn draw_committee(env: &Env) -> Result<Vec<Address>, Error> {
+        let providers = Self::get_providers(env.clone());
+        let mut weights = Vec::new(env);
+        let mut total: u64 = 0;
+        for provider in providers.iter() {
+            let weight = Self::get_provider_weight(env.clone(), provider);
+            weights.push_back(weight);
+            total += u64::from(weight);
+        }
+
+        let size = Self::get_committee_size(env.clone());
+        let mut committee = Vec::new(env);
+        let mut checks = 0;
+        while committee.len() < size && total > 0 && checks < MAX_ELIGIBILITY_CHECKS {
+            let mut pick: u64 = env.prng().gen_range(0..total);
+            let mut index = 0;
+            while pick >= u64::from(weights.get_unchecked(index)) {
+                pick -= u64::from(weights.get_unchecked(index));
+                index += 1;
+            }
+            total -= u64::from(weights.get_unchecked(index));
+            weights.set(index, 0);
+
+            let provider = providers.get_unchecked(index);
+            checks += 1;
+            if Self::is_eligible(env, &provider)? {
+                committee.push_back(provider);
+            }
+        }
+        if committee.is_empty() {
+            return Err(Error::NoEligibleProviders);
+        }
+        Ok(committee)
+    }
+
+    /// Whether the registry reports `provider` as eligible to take work.
+    fn is_eligible(env: &Env, provider: &Address) -> Result<bool, Error> {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Registry)
+            .ok_or(Error::NotInitialized)?;
+        Ok(env.invoke_contract(
+            &registry,
+            &Symbol::new(env, "is_eligible_operator"),
+            vec![env, provider.into_val(env)],
+        ))
+    }
+
+    /// Whether `provider` may work on `request`.
+    fn is_assigned(request: &VerificationRequest, provider: &Address) -> bool {
+        request.assigned.contains(provider)
+    }
+
+    /// Whether someone other than `provider` holds an active lease on `request`.
+    fn leased_to_other(env: &Env, request: &VerificationRequest, provider: &Address) -> bool {
+        match &request.leased_to {
+            Some(holder) => holder != provider && request.lease_expires >= env.ledger().sequence(),
+            None => false,
+        }
+    }
+
+    /// Load a request that is still awaiting fulfillment.
+    fn pending_request(env: &Env, request_id: u64) -> Option<VerificationRequest> {
+        env.storage()
+            .temporary()
+            .get::<_, VerificationRequest>(&DataKey::Request(request_id))
+            .filter(|request| {
+                request.state == RequestState::Pending
+                    && request.deadline >= env.ledger().sequence()
+            })
+    }
+
+    fn pending_head(env: &Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingHead)
+            .unwrap_or(0)
+    }
+
+    /// Move the pending-queue head past up to `PENDING_HEAD_STEPS` settled
+    /// requests, so that each submission keeps it ahead of the backlog.
+    fn advance_pending_head(env: &Env) {
+        let last_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRequestId)
+            .unwrap_or(0);
+        let start = Self::pending_head(env);
+        let mut head = start;
+        for _ in 0..PENDING_HEAD_STEPS {
+            if head >= last_id || Self::pending_request(env, head + 1).is_some() {
+                break;
+            }
+            head += 1;
+        }
+        if head != start {
+            env.storage().persistent().set(&DataKey::PendingHead, &head);
+        }
+    }
+
+    /// Load a pending request from temporary storage, telling finalized and
+    /// lapsed ids apart from ids that were never issued.
+    fn load_request(env: &Env, request_id: u64) -> Result<VerificationRequest, Error> {
+        if let Some(request) = env.storage().temporary().get(&DataKey::Request(request_id)) {
+            return Ok(request);
+        }
+        if env.storage().persistent().has(&DataKey::Archived(request_id)) {
+            return Err(Error::AlreadyFinalized);
+        }
+        let last_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRequestId)
+            .unwrap_or(0);
+        if request_id >= 1 && request_id <= last_id {
+            Err(Error::RequestExpired)
+        } else {
+            Err(Error::RequestNotFound)
+        }
+    }
 }
 
 mod test;
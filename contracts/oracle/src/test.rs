@@ -1,7 +1,18 @@
 #![cfg(test)]
+extern crate std;
 
 use super::*;
-use soroban_sdk::{testutils::storage::Temporary as _, testutils::Address as _, Address, BytesN, Env};
+use soroban_sdk::{
+    testutils::storage::Persistent as _, testutils::storage::Temporary as _,
+    testutils::Address as _, testutils::Ledger as _, Address, BytesN, Env,
+};
+
+fn sha256_hash(env: &Env, fill: u8) -> ContentHash {
+    ContentHash {
+        algorithm: HashAlgorithm::Sha256,
+        digest: BytesN::from_array(env, &[fill; 32]),
+    }
+}
 
 #[test]
 fn test_init() {
@@ -24,15 +35,21 @@ fn test_submit_request_stores_pending_in_temporary_storage() {
     let contract_id = env.register(Contract, ());
     let client = ContractClient::new(&env, &contract_id);
 
-    // Initialization not strictly required for submit_request, but keep setup consistent.
-    let registry = Address::generate(&env);
+    // Requests are assigned to providers the registry reports as eligible.
+    let registry = env.register(RegistryMock, ());
     let provenance = Address::generate(&env);
     let admin = Address::generate(&env);
     client.init(&registry, &provenance, &admin);
+    client.add_provider(&Address::generate(&env));
 
-    let content_hash = BytesN::from_array(&env, &[5; 32]);
+    let content_hash = sha256_hash(&env, 5);
 
-    let request_id = client.submit_request(&content_hash);
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &content_hash,
+        &Job::ContentIntegrity,
+        &None,
+    );
     assert_eq!(request_id, 1);
 
     // Verify the request is stored in temporary storage with Pending state.
@@ -64,16 +81,30 @@ fn test_submit_request_generates_unique_ids() {
     let contract_id = env.register(Contract, ());
     let client = ContractClient::new(&env, &contract_id);
 
-    let registry = Address::generate(&env);
+    let registry = env.register(RegistryMock, ());
     let provenance = Address::generate(&env);
     let admin = Address::generate(&env);
     client.init(&registry, &provenance, &admin);
-
-    let hash1 = BytesN::from_array(&env, &[1; 32]);
-    let hash2 = BytesN::from_array(&env, &[2; 32]);
-
-    let id1 = client.submit_request(&hash1);
-    let id2 = client.submit_request(&hash2);
+    client.add_provider(&Address::generate(&env));
+
+    let hash1 = sha256_hash(&env, 1);
+    let hash2 = ContentHash {
+        algorithm: HashAlgorithm::Keccak256,
+        digest: BytesN::from_array(&env, &[2; 32]),
+    };
+
+    let id1 = client.submit_request(
+        &Address::generate(&env),
+        &hash1,
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let id2 = client.submit_request(
+        &Address::generate(&env),
+        &hash2,
+        &Job::ContentIntegrity,
+        &None,
+    );
 
     assert_eq!(id1, 1);
     assert_eq!(id2, 2);
@@ -92,6 +123,7 @@ fn test_submit_request_generates_unique_ids() {
     assert_eq!(req2.id, id2);
     assert_eq!(req1.content_hash, hash1);
     assert_eq!(req2.content_hash, hash2);
+    assert_eq!(req2.content_hash.algorithm, HashAlgorithm::Keccak256);
 }
 
 #[test]
@@ -182,6 +214,34 @@ impl RegistryMock {
         let hash_bytes = hash.to_array();
         hash_bytes[0] == 88
     }
+
+    // Every operator is eligible unless marked otherwise. Counts the checks.
+    pub fn is_eligible_operator(env: Env, operator: Address) -> bool {
+        let checks: u32 = env.storage().instance().get(&0u32).unwrap_or(0);
+        env.storage().instance().set(&0u32, &(checks + 1));
+        !env.storage().instance().has(&operator)
+    }
+
+    pub fn eligibility_checks(env: Env) -> u32 {
+        env.storage().instance().get(&0u32).unwrap_or(0)
+    }
+
+    pub fn set_ineligible(env: Env, operator: Address) {
+        env.storage().instance().set(&operator, &true);
+    }
+
+    // Every signing key belongs to the operator presenting it unless bound
+    // to another one.
+    pub fn is_operator_key(env: Env, operator: Address, key: BytesN<32>) -> bool {
+        env.storage()
+            .instance()
+            .get::<_, Address>(&key)
+            .is_none_or(|owner| owner == operator)
+    }
+
+    pub fn bind_key(env: Env, key: BytesN<32>, operator: Address) {
+        env.storage().instance().set(&key, &operator);
+    }
 }
 
 #[test]
@@ -271,8 +331,6 @@ fn test_verify_attestation_not_initialized() {
     assert_eq!(result, Err(Ok(Error::NotInitialized)));
 }
 
-
-
 #[test]
 fn test_verify_tee_hash_success() {
     let env = Env::default();
@@ -345,3 +403,2209 @@ fn test_verify_tee_hash_registry_call_failure() {
     // OracleError::RegistryCallFailed.
     assert_eq!(result, Err(Ok(OracleError::RegistryCallFailed)));
 }
+
+// ---------------------------------------------------------------------------
+// Fulfillment and expiry
+// ---------------------------------------------------------------------------
+
+/// Initialises the oracle against the registry mock and registers a provider.
+#[soroban_sdk::contract]
+pub struct ProvenanceMock;
+
+#[soroban_sdk::contractimpl]
+impl ProvenanceMock {
+    pub fn mint(
+        env: Env,
+        _minter: Address,
+        to: Address,
+        details: provenance::CertificateDetails,
+    ) -> u64 {
+        let next: u64 = env.storage().instance().get(&0u32).unwrap_or(0) + 1;
+        env.storage().instance().set(&0u32, &next);
+        env.storage().instance().set(&(1u32, next), &(to, details));
+        next
+    }
+
+    pub fn get_mint(
+        env: Env,
+        certificate_id: u64,
+    ) -> Option<(Address, provenance::CertificateDetails)> {
+        env.storage().instance().get(&(1u32, certificate_id))
+    }
+}
+
+fn setup_fulfillment(env: &Env) -> (ContractClient<'_>, Address) {
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(env, &contract_id);
+    let registry_id = env.register(RegistryMock, ());
+    let provenance_id = env.register(ProvenanceMock, ());
+    client.init(&registry_id, &provenance_id, &Address::generate(env));
+
+    let provider = Address::generate(env);
+    client.add_provider(&provider);
+    (client, provider)
+}
+
+fn live_request(client: &ContractClient, request_id: u64) -> VerificationRequest {
+    match client.get_request(&request_id) {
+        RequestRecord::Live(request) => request,
+        other => panic!("request {request_id} is not live: {other:?}"),
+    }
+}
+
+fn archived_request(client: &ContractClient, request_id: u64) -> ArchivedRequest {
+    match client.get_request(&request_id) {
+        RequestRecord::Archived(record) => record,
+        other => panic!("request {request_id} is not archived: {other:?}"),
+    }
+}
+
+/// Signs a fulfillment with a TEE key the registry mock trusts.
+fn signed_fulfillment(env: &Env, request_id: u64, verified: bool) -> (Fulfillment, BytesN<64>) {
+    signed_result(env, request_id, verified, JobResult::ContentIntegrity)
+}
+
+/// Signs a fulfillment carrying typed job findings.
+fn signed_result(
+    env: &Env,
+    request_id: u64,
+    verified: bool,
+    result: JobResult,
+) -> (Fulfillment, BytesN<64>) {
+    let (signing_key, signer) = create_keypair(env, 1);
+    let fulfillment = Fulfillment {
+        request_id,
+        verified,
+        result,
+        signer,
+        tee_hash: BytesN::from_array(env, &[88; 32]),
+    };
+    let payload = fulfillment.clone().to_xdr(env);
+    let mut buf = std::vec![0u8; payload.len() as usize];
+    payload.copy_into_slice(&mut buf);
+    let signature = sign_payload(env, &signing_key, &buf);
+    (fulfillment, signature)
+}
+
+#[test]
+fn test_fulfill_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    let verified_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let rejected_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 2),
+        &Job::ContentIntegrity,
+        &None,
+    );
+
+    let (fulfillment, signature) = signed_fulfillment(&env, verified_id, true);
+    assert_eq!(
+        client.fulfill_request(&provider, &fulfillment, &signature),
+        RequestState::Verified
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, rejected_id, false);
+    assert_eq!(
+        client.fulfill_request(&provider, &fulfillment, &signature),
+        RequestState::Rejected
+    );
+    assert_eq!(
+        archived_request(&client, verified_id).state,
+        RequestState::Verified
+    );
+    assert_eq!(
+        client.try_fulfill_request(&provider, &fulfillment, &signature),
+        Err(Ok(Error::AlreadyFinalized))
+    );
+}
+
+#[test]
+fn test_fulfill_request_requires_provider() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _provider) = setup_fulfillment(&env);
+
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.try_fulfill_request(&Address::generate(&env), &fulfillment, &signature),
+        Err(Ok(Error::UnauthorizedProvider))
+    );
+}
+
+#[test]
+fn test_fulfill_request_after_deadline_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    client.set_request_ttl(&10);
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    assert_eq!(live_request(&client, request_id).deadline, 110);
+
+    env.ledger().with_mut(|li| li.sequence_number = 111);
+    assert_eq!(
+        live_request(&client, request_id).state,
+        RequestState::Expired
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.fulfill_request(&provider, &fulfillment, &signature),
+        RequestState::Expired
+    );
+    assert_eq!(
+        client.try_fulfill_request(&provider, &fulfillment, &signature),
+        Err(Ok(Error::AlreadyFinalized))
+    );
+}
+
+/// Lapsed ids report `RequestExpired`; ids never issued report `RequestNotFound`.
+#[test]
+fn test_get_request_expired_versus_unknown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _provider) = setup_fulfillment(&env);
+    client.set_request_ttl(&10);
+
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    // Expired requests are held for settlement before they lapse.
+    env.ledger()
+        .with_mut(|li| li.sequence_number += 10 + SETTLEMENT_LEDGERS + 1);
+
+    assert_eq!(
+        client.try_get_request(&request_id),
+        Err(Ok(Error::RequestExpired))
+    );
+    assert_eq!(client.try_get_request(&2), Err(Ok(Error::RequestNotFound)));
+    assert_eq!(client.try_get_request(&0), Err(Ok(Error::RequestNotFound)));
+}
+
+#[test]
+fn test_set_request_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _provider) = setup_fulfillment(&env);
+
+    assert_eq!(client.get_request_ttl(), 100);
+    assert_eq!(client.try_set_request_ttl(&0), Err(Ok(Error::InvalidTtl)));
+    assert_eq!(
+        client.try_set_request_ttl(&u32::MAX),
+        Err(Ok(Error::InvalidTtl))
+    );
+    client.set_request_ttl(&500);
+    assert_eq!(client.get_request_ttl(), 500);
+
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let request = live_request(&client, request_id);
+    assert_eq!(request.deadline, env.ledger().sequence() + 500);
+}
+
+// ---------------------------------------------------------------------------
+// Cancellation and fees
+// ---------------------------------------------------------------------------
+
+/// Registers a Stellar asset as the request fee and funds `requester` with it.
+fn setup_fee(env: &Env, client: &ContractClient, requester: &Address) -> token::Client<'static> {
+    let issuer = Address::generate(env);
+    let asset = env.register_stellar_asset_contract_v2(issuer);
+    token::StellarAssetClient::new(env, &asset.address()).mint(requester, &1_000);
+    client.set_request_fee(&asset.address(), &100);
+    token::Client::new(env, &asset.address())
+}
+
+#[test]
+fn test_cancel_request_refunds_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    let requester = Address::generate(&env);
+    let token = setup_fee(&env, &client, &requester);
+
+    let request_id = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    assert_eq!(token.balance(&requester), 900);
+    assert_eq!(live_request(&client, request_id).fee, 100);
+
+    client.cancel_request(&request_id);
+    assert_eq!(env.auths()[0].0, requester);
+    assert_eq!(token.balance(&requester), 1_000);
+    assert_eq!(
+        archived_request(&client, request_id).state,
+        RequestState::Cancelled
+    );
+
+    // Cancelled requests can be neither fulfilled nor cancelled again.
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.try_fulfill_request(&provider, &fulfillment, &signature),
+        Err(Ok(Error::AlreadyFinalized))
+    );
+    assert_eq!(
+        client.try_cancel_request(&request_id),
+        Err(Ok(Error::AlreadyFinalized))
+    );
+    assert_eq!(token.balance(&requester), 1_000);
+}
+
+#[test]
+fn test_fulfill_request_pays_fee_to_provider() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    let requester = Address::generate(&env);
+    let token = setup_fee(&env, &client, &requester);
+
+    let request_id = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, false);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+    assert_eq!(token.balance(&provider), 100);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(
+        client.try_cancel_request(&request_id),
+        Err(Ok(Error::AlreadyFinalized))
+    );
+}
+
+/// Expired requests return their fee to the requester and cannot be cancelled.
+#[test]
+fn test_expired_request_refunds_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    let requester = Address::generate(&env);
+    let token = setup_fee(&env, &client, &requester);
+    client.set_request_ttl(&10);
+
+    let request_id = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    assert_eq!(
+        client.try_cancel_request(&request_id),
+        Err(Ok(Error::AlreadyFinalized))
+    );
+
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.fulfill_request(&provider, &fulfillment, &signature),
+        RequestState::Expired
+    );
+    assert_eq!(token.balance(&requester), 1_000);
+    assert_eq!(token.balance(&provider), 0);
+}
+
+/// With the default TTL the request outlives its deadline long enough for the
+/// requester to recover the fee.
+#[test]
+fn test_requester_claims_refund_after_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    let requester = Address::generate(&env);
+    let token = setup_fee(&env, &client, &requester);
+
+    let request_id = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    assert_eq!(
+        client.try_claim_refund(&request_id),
+        Err(Ok(Error::RequestNotExpired))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number += 101);
+    assert_eq!(
+        live_request(&client, request_id).state,
+        RequestState::Expired
+    );
+    assert_eq!(
+        client.try_cancel_request(&request_id),
+        Err(Ok(Error::AlreadyFinalized))
+    );
+    client.claim_refund(&request_id);
+    assert_eq!(token.balance(&requester), 1_000);
+    assert_eq!(
+        archived_request(&client, request_id).state,
+        RequestState::Expired
+    );
+
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.try_fulfill_request(&provider, &fulfillment, &signature),
+        Err(Ok(Error::AlreadyFinalized))
+    );
+    assert_eq!(token.balance(&provider), 0);
+}
+
+#[test]
+fn test_set_request_fee_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _provider) = setup_fulfillment(&env);
+    let token = Address::generate(&env);
+
+    assert_eq!(client.get_request_fee(), None);
+    assert_eq!(
+        client.try_set_request_fee(&token, &-1),
+        Err(Ok(Error::InvalidFee))
+    );
+
+    // A zero fee escrows nothing.
+    client.set_request_fee(&token, &0);
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let request = live_request(&client, request_id);
+    assert_eq!(request.fee_token, None);
+    assert_eq!(request.fee, 0);
+}
+
+// ---------------------------------------------------------------------------
+// Archive
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_finalized_request_is_archived() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    env.ledger().with_mut(|li| li.sequence_number = 50);
+    let content_hash = sha256_hash(&env, 1);
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &content_hash,
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    env.ledger().with_mut(|li| li.sequence_number = 60);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+
+    let attestation_hash: BytesN<32> = env.crypto().sha256(&fulfillment.to_xdr(&env)).into();
+    assert_eq!(
+        archived_request(&client, request_id),
+        ArchivedRequest {
+            id: request_id,
+            content_hash,
+            state: RequestState::Verified,
+            provider: Some(provider.clone()),
+            attestation_hash: Some(attestation_hash),
+            certificate_id: Some(1),
+            callback_status: CallbackStatus::NotRequested,
+            finalized_at: 60,
+        }
+    );
+
+    let (temporary, ttl) = env.as_contract(&client.address, || {
+        (
+            env.storage().temporary().has(&DataKey::Request(request_id)),
+            env.storage()
+                .persistent()
+                .get_ttl(&DataKey::Archived(request_id)),
+        )
+    });
+    assert!(!temporary);
+    assert!(ttl >= ARCHIVE_TTL_LEDGERS - 1);
+
+    // The record outlives the request TTL.
+    env.ledger().with_mut(|li| li.sequence_number += 10_000);
+    assert_eq!(
+        archived_request(&client, request_id).state,
+        RequestState::Verified
+    );
+}
+
+#[test]
+fn test_cancelled_and_expired_requests_are_archived() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    client.set_request_ttl(&10);
+
+    let cancelled = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let expired = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 2),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    client.cancel_request(&cancelled);
+
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    let (fulfillment, signature) = signed_fulfillment(&env, expired, true);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+
+    let record = archived_request(&client, cancelled);
+    assert_eq!(record.state, RequestState::Cancelled);
+    assert_eq!(record.provider, None);
+    let record = archived_request(&client, expired);
+    assert_eq!(record.state, RequestState::Expired);
+    assert_eq!(record.attestation_hash, None);
+}
+
+// ---------------------------------------------------------------------------
+// Request lifecycle events
+// ---------------------------------------------------------------------------
+
+fn last_events(env: &Env) -> std::string::String {
+    std::format!("{:#?}", soroban_sdk::testutils::Events::all(&env.events()))
+}
+
+#[test]
+fn test_request_lifecycle_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    client.set_request_ttl(&10);
+
+    let verified = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let events = last_events(&env);
+    assert!(events.contains("request_submitted"), "{events}");
+    assert!(events.contains("0101010101010101"), "{events}");
+
+    let (fulfillment, signature) = signed_fulfillment(&env, verified, true);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+    let events = last_events(&env);
+    assert!(events.contains("request_verified"), "{events}");
+
+    let rejected = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 2),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, rejected, false);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+    let events = last_events(&env);
+    assert!(events.contains("request_rejected"), "{events}");
+
+    let cancelled = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 3),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    client.cancel_request(&cancelled);
+    let events = last_events(&env);
+    assert!(events.contains("request_cancelled"), "{events}");
+
+    let expired = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 4),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    let (fulfillment, signature) = signed_fulfillment(&env, expired, true);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+    let events = last_events(&env);
+    assert!(events.contains("request_expired"), "{events}");
+}
+
+// ---------------------------------------------------------------------------
+// Pending queue
+// ---------------------------------------------------------------------------
+
+fn pending_ids(client: &ContractClient, cursor: u64, limit: u32) -> std::vec::Vec<u64> {
+    client
+        .next_pending(&cursor, &limit)
+        .requests
+        .iter()
+        .map(|pending| pending.id)
+        .collect()
+}
+
+#[test]
+fn test_next_pending_pages_outstanding_requests() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    for fill in 1..=4 {
+        client.submit_request(
+            &Address::generate(&env),
+            &sha256_hash(&env, fill),
+            &Job::ContentIntegrity,
+            &None,
+        );
+    }
+    let (fulfillment, signature) = signed_fulfillment(&env, 2, true);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+    client.cancel_request(&4);
+
+    let page = client.next_pending(&0, &10);
+    assert_eq!(page.requests.len(), 2);
+    assert_eq!(page.cursor, 4);
+    assert_eq!(
+        page.requests.get(0).unwrap(),
+        PendingRequest {
+            id: 1,
+            content_hash: sha256_hash(&env, 1),
+            job: Job::ContentIntegrity,
+            assigned: soroban_sdk::vec![&env, provider.clone()],
+        }
+    );
+    assert_eq!(pending_ids(&client, 0, 1), [1]);
+    assert_eq!(client.next_pending(&0, &1).cursor, 1);
+    assert_eq!(pending_ids(&client, 1, 1), [3]);
+    assert!(pending_ids(&client, 3, 10).is_empty());
+}
+
+#[test]
+fn test_pending_queue_prunes_expired_requests() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _provider) = setup_fulfillment(&env);
+    client.set_request_ttl(&10);
+
+    client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    assert!(pending_ids(&client, 0, 10).is_empty());
+
+    client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 2),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let head: u64 = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingHead)
+            .unwrap()
+    });
+    assert_eq!(head, 1);
+    assert_eq!(pending_ids(&client, 0, 10), [2]);
+}
+
+/// Each call inspects a bounded range of ids, however long the backlog of
+/// settled requests, and hands back a cursor to resume from.
+#[test]
+fn test_next_pending_bounds_each_scan() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _provider) = setup_fulfillment(&env);
+
+    // Pretend a long run of requests has already been settled.
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRequestId, &(3 * MAX_PENDING_SCAN));
+    });
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+
+    // Scanning starts after the head, which the submission moved forward.
+    let head: u64 = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingHead)
+            .unwrap()
+    });
+    let mut cursor = 0;
+    let mut empty_pages = 0;
+    loop {
+        let page = client.next_pending(&cursor, &10);
+        assert!(page.cursor - cursor.max(head) <= MAX_PENDING_SCAN);
+        cursor = page.cursor;
+        if let Some(pending) = page.requests.get(0) {
+            assert_eq!(pending.id, request_id);
+            break;
+        }
+        empty_pages += 1;
+    }
+    assert_eq!(cursor, request_id);
+    assert!(empty_pages >= 2);
+    assert!(client.next_pending(&cursor, &10).requests.is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// Leases
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_claim_request_reserves_fulfillment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, holder) = setup_fulfillment(&env);
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    assert_eq!(client.claim_request(&holder, &request_id), 160);
+    let events = last_events(&env);
+    assert!(events.contains("request_claimed"), "{events}");
+
+    let request = live_request(&client, request_id);
+    assert_eq!(request.leased_to, Some(holder.clone()));
+    assert_eq!(request.lease_expires, 160);
+    assert!(pending_ids(&client, 0, 10).is_empty());
+
+    // The holder may renew its lease.
+    env.ledger().with_mut(|li| li.sequence_number = 150);
+    assert_eq!(client.claim_request(&holder, &request_id), 200);
+
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.fulfill_request(&holder, &fulfillment, &signature),
+        RequestState::Verified
+    );
+}
+
+/// A lapsed lease returns the request to the queue.
+#[test]
+fn test_lapsed_lease_releases_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, holder) = setup_fulfillment(&env);
+
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let lease_expires = client.claim_request(&holder, &request_id);
+    assert!(pending_ids(&client, 0, 10).is_empty());
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number = lease_expires + 1);
+    assert_eq!(pending_ids(&client, 0, 10), [request_id]);
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, false);
+    assert_eq!(
+        client.fulfill_request(&holder, &fulfillment, &signature),
+        RequestState::Rejected
+    );
+}
+
+#[test]
+fn test_claim_request_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    client.set_request_ttl(&10);
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    assert_eq!(
+        client.try_claim_request(&Address::generate(&env), &request_id),
+        Err(Ok(Error::UnauthorizedProvider))
+    );
+
+    // Leases never outlast the request deadline.
+    assert_eq!(client.claim_request(&provider, &request_id), 110);
+
+    env.ledger().with_mut(|li| li.sequence_number = 111);
+    assert_eq!(
+        client.try_claim_request(&provider, &request_id),
+        Err(Ok(Error::RequestExpired))
+    );
+    assert_eq!(
+        client.try_claim_request(&provider, &99),
+        Err(Ok(Error::RequestNotFound))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Provider assignment
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_submit_request_assigns_weighted_committee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, first) = setup_fulfillment(&env);
+    let second = Address::generate(&env);
+    let benched = Address::generate(&env);
+    client.add_provider(&second);
+    client.add_provider(&benched);
+    client.set_provider_weight(&benched, &0);
+    client.set_committee_size(&3);
+
+    // Zero-weight providers are never drawn, even for a larger committee.
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let assigned = live_request(&client, request_id).assigned;
+    assert_eq!(assigned.len(), 2);
+    assert!(assigned.contains(&first));
+    assert!(assigned.contains(&second));
+    assert!(!assigned.contains(&benched));
+}
+
+#[test]
+fn test_assignment_follows_provider_weight() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, light) = setup_fulfillment(&env);
+    let heavy = Address::generate(&env);
+    client.add_provider(&heavy);
+    client.set_provider_weight(&light, &1);
+    client.set_provider_weight(&heavy, &99);
+
+    let mut heavy_draws = 0;
+    for fill in 0..100 {
+        let request_id = client.submit_request(
+            &Address::generate(&env),
+            &sha256_hash(&env, fill),
+            &Job::ContentIntegrity,
+            &None,
+        );
+        let assigned = live_request(&client, request_id).assigned;
+        assert_eq!(assigned.len(), 1);
+        if assigned.contains(&heavy) {
+            heavy_draws += 1;
+        }
+    }
+    assert!(
+        heavy_draws >= 90,
+        "heavy provider drawn {heavy_draws} times"
+    );
+}
+
+#[test]
+fn test_only_assigned_providers_may_work() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, first) = setup_fulfillment(&env);
+    let second = Address::generate(&env);
+    client.add_provider(&second);
+
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let assigned = live_request(&client, request_id).assigned.get(0).unwrap();
+    let outsider = if assigned == first { second } else { first };
+
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.try_claim_request(&outsider, &request_id),
+        Err(Ok(Error::NotAssigned))
+    );
+    assert_eq!(
+        client.try_fulfill_request(&outsider, &fulfillment, &signature),
+        Err(Ok(Error::NotAssigned))
+    );
+    assert_eq!(
+        client.fulfill_request(&assigned, &fulfillment, &signature),
+        RequestState::Verified
+    );
+}
+
+/// An assignee that stays silent loses the request once its assignment
+/// lapses: another eligible provider may claim it and take over, after which
+/// its lease keeps the former assignee out.
+#[test]
+fn test_lapsed_assignment_can_be_taken_over() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, first) = setup_fulfillment(&env);
+    let second = Address::generate(&env);
+    let barred = Address::generate(&env);
+    client.add_provider(&second);
+    client.add_provider(&barred);
+    registry_mock(&env, &client).set_ineligible(&barred);
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let request = live_request(&client, request_id);
+    assert_eq!(request.assigned_until, 160);
+    let assigned = request.assigned.get(0).unwrap();
+    let outsider = if assigned == first { second } else { first };
+
+    assert_eq!(
+        client.try_claim_request(&outsider, &request_id),
+        Err(Ok(Error::NotAssigned))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 161);
+    assert_eq!(
+        client.try_claim_request(&barred, &request_id),
+        Err(Ok(Error::NotAssigned))
+    );
+    assert_eq!(client.claim_request(&outsider, &request_id), 200);
+    assert_eq!(
+        live_request(&client, request_id).assigned,
+        soroban_sdk::vec![&env, outsider.clone()]
+    );
+
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.try_claim_request(&assigned, &request_id),
+        Err(Ok(Error::RequestLeased))
+    );
+    assert_eq!(
+        client.try_fulfill_request(&assigned, &fulfillment, &signature),
+        Err(Ok(Error::RequestLeased))
+    );
+    assert_eq!(
+        client.fulfill_request(&outsider, &fulfillment, &signature),
+        RequestState::Verified
+    );
+}
+
+/// Providers the registry does not report as eligible are never drawn, and
+/// a request nobody is eligible for is refused rather than left open.
+#[test]
+fn test_submit_request_requires_eligible_provider() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let registry_id = env.register(RegistryMock, ());
+    let registry = RegistryMockClient::new(&env, &registry_id);
+    client.init(
+        &registry_id,
+        &Address::generate(&env),
+        &Address::generate(&env),
+    );
+
+    let submit = |fill: u8| {
+        client.try_submit_request(
+            &Address::generate(&env),
+            &sha256_hash(&env, fill),
+            &Job::ContentIntegrity,
+            &None,
+        )
+    };
+    assert_eq!(submit(1), Err(Ok(Error::NoEligibleProviders)));
+
+    let eligible = Address::generate(&env);
+    let barred = Address::generate(&env);
+    client.add_provider(&eligible);
+    client.add_provider(&barred);
+    registry.set_ineligible(&barred);
+    client.set_committee_size(&2);
+
+    let request_id = submit(1).unwrap().unwrap();
+    assert_eq!(
+        live_request(&client, request_id).assigned,
+        soroban_sdk::vec![&env, eligible.clone()]
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.try_fulfill_request(&barred, &fulfillment, &signature),
+        Err(Ok(Error::NotAssigned))
+    );
+
+    registry.set_ineligible(&eligible);
+    assert_eq!(submit(2), Err(Ok(Error::NoEligibleProviders)));
+}
+
+fn registry_mock<'a>(env: &'a Env, client: &ContractClient) -> RegistryMockClient<'a> {
+    let registry: Address = env.as_contract(&client.address, || {
+        env.storage().instance().get(&DataKey::Registry).unwrap()
+    });
+    RegistryMockClient::new(env, &registry)
+}
+
+/// Assigned providers can only attest with TEE keys the registry lists as
+/// theirs, whether fulfilling, batching or revealing.
+#[test]
+fn test_attestations_require_providers_own_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, signer) = create_keypair(&env, 1);
+
+    let (client, provider) = setup_fulfillment(&env);
+    registry_mock(&env, &client).bind_key(&signer, &Address::generate(&env));
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.try_fulfill_request(&provider, &fulfillment, &signature),
+        Err(Ok(Error::UnauthorizedSigner))
+    );
+    let leaves = [BatchLeaf {
+        request_id,
+        verified: true,
+        result: JobResult::ContentIntegrity,
+    }];
+    let (batch, signature, proofs) = signed_batch(&env, &leaves, [88; 32]);
+    assert_eq!(
+        client.try_fulfill_batch(&provider, &batch, &signature, &proof_vec(&env, &proofs)),
+        Err(Ok(Error::UnauthorizedSigner))
+    );
+
+    let (client, [first, ..], request_id) = setup_round(&env);
+    let (fulfillment, salt, signature) = commit_vote(&env, &client, &first, request_id, true);
+    registry_mock(&env, &client).bind_key(&signer, &Address::generate(&env));
+    env.ledger().with_mut(|li| li.sequence_number = 121);
+    assert_eq!(
+        client.try_reveal_result(&first, &fulfillment, &salt, &signature),
+        Err(Ok(Error::UnauthorizedSigner))
+    );
+    registry_mock(&env, &client).bind_key(&signer, &first);
+    client.reveal_result(&first, &fulfillment, &salt, &signature);
+}
+
+/// The registry is asked only about drawn providers, however many are
+/// registered, and the number of providers is capped.
+#[test]
+fn test_committee_draw_checks_only_drawn_providers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _provider) = setup_fulfillment(&env);
+    let registry = registry_mock(&env, &client);
+    for _ in 1..MAX_PROVIDERS {
+        client.add_provider(&Address::generate(&env));
+    }
+    assert_eq!(
+        client.try_add_provider(&Address::generate(&env)),
+        Err(Ok(Error::TooManyProviders))
+    );
+
+    client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    assert_eq!(registry.eligibility_checks(), 1);
+
+    // Ineligible draws are replaced, within a bounded number of checks.
+    client.set_committee_size(&MAX_COMMITTEE_SIZE);
+    let providers = client.get_providers();
+    for provider in providers.iter().skip(MAX_COMMITTEE_SIZE as usize) {
+        registry.set_ineligible(&provider);
+    }
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 2),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let checks = registry.eligibility_checks() - 1;
+    assert!(checks <= MAX_ELIGIBILITY_CHECKS);
+    for member in live_request(&client, request_id).assigned.iter() {
+        assert!(providers.first_index_of(&member).unwrap() < MAX_COMMITTEE_SIZE);
+    }
+}
+
+#[test]
+fn test_provider_weight_and_committee_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    assert_eq!(client.get_provider_weight(&provider), 100);
+    assert_eq!(client.get_committee_size(), 1);
+    assert_eq!(
+        client.try_set_committee_size(&0),
+        Err(Ok(Error::InvalidCommitteeSize))
+    );
+    assert_eq!(
+        client.try_set_committee_size(&(MAX_COMMITTEE_SIZE + 1)),
+        Err(Ok(Error::InvalidCommitteeSize))
+    );
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_provider_weight(&stranger, &5),
+        Err(Ok(Error::UnauthorizedProvider))
+    );
+
+    client.add_provider(&provider);
+    assert_eq!(client.get_providers().len(), 1);
+    client.remove_provider(&provider);
+    assert!(client.get_providers().is_empty());
+    assert_eq!(client.get_provider_weight(&provider), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Commit–reveal
+// ---------------------------------------------------------------------------
+
+/// Submits a request at ledger 100 assigned to a committee of three
+/// providers. The commit window closes after ledger 120 and the request
+/// expires after ledger 200.
+fn setup_round(env: &Env) -> (ContractClient<'_>, [Address; 3], u64) {
+    let (client, first) = setup_fulfillment(env);
+    let second = Address::generate(env);
+    let third = Address::generate(env);
+    client.add_provider(&second);
+    client.add_provider(&third);
+    client.set_committee_size(&3);
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    let request_id = client.submit_request(
+        &Address::generate(env),
+        &sha256_hash(env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    (client, [first, second, third], request_id)
+}
+
+fn commitment(
+    env: &Env,
+    provider: &Address,
+    fulfillment: &Fulfillment,
+    salt: &BytesN<32>,
+) -> BytesN<32> {
+    let mut preimage = provider.clone().to_xdr(env);
+    preimage.append(&fulfillment.clone().to_xdr(env));
+    preimage.append(&salt.clone().into());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Commits `verified` for `provider`, returning what it needs to reveal.
+fn commit_vote(
+    env: &Env,
+    client: &ContractClient,
+    provider: &Address,
+    request_id: u64,
+    verified: bool,
+) -> (Fulfillment, BytesN<32>, BytesN<64>) {
+    let (fulfillment, signature) = signed_fulfillment(env, request_id, verified);
+    let salt = env.crypto().sha256(&provider.clone().to_xdr(env)).into();
+    client.commit_result(
+        provider,
+        &request_id,
+        &commitment(env, provider, &fulfillment, &salt),
+    );
+    (fulfillment, salt, signature)
+}
+
+#[test]
+fn test_commit_reveal_majority_decides() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, providers, request_id) = setup_round(&env);
+    let [first, second, third] = providers.clone();
+    let request = live_request(&client, request_id);
+    assert_eq!(request.assigned.len(), 3);
+    assert_eq!(request.commit_ends, 120);
+
+    // Committees cannot settle through a single provider.
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.try_fulfill_request(&first, &fulfillment, &signature),
+        Err(Ok(Error::CommitRevealRequired))
+    );
+    assert_eq!(
+        client.try_claim_request(&first, &request_id),
+        Err(Ok(Error::CommitRevealRequired))
+    );
+
+    let votes = [
+        commit_vote(&env, &client, &first, request_id, true),
+        commit_vote(&env, &client, &second, request_id, false),
+        commit_vote(&env, &client, &third, request_id, true),
+    ];
+    assert_eq!(client.get_round(&request_id).commitments.len(), 3);
+
+    env.ledger().with_mut(|li| li.sequence_number = 121);
+    for (provider, (fulfillment, salt, signature)) in providers.iter().zip(votes.iter()) {
+        client.reveal_result(provider, fulfillment, salt, signature);
+    }
+    assert_eq!(client.finalize_round(&request_id), RequestState::Verified);
+
+    let record = archived_request(&client, request_id);
+    assert_eq!(record.state, RequestState::Verified);
+    assert_eq!(record.provider, Some(first));
+    assert!(client.get_round(&request_id).reveals.is_empty());
+}
+
+#[test]
+fn test_commit_reveal_windows() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, [first, second, _], request_id) = setup_round(&env);
+
+    let (fulfillment, salt, signature) = commit_vote(&env, &client, &first, request_id, true);
+    assert_eq!(
+        client.try_commit_result(
+            &first,
+            &request_id,
+            &commitment(&env, &first, &fulfillment, &salt)
+        ),
+        Err(Ok(Error::AlreadyCommitted))
+    );
+    assert_eq!(
+        client.try_reveal_result(&first, &fulfillment, &salt, &signature),
+        Err(Ok(Error::CommitWindowOpen))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 121);
+    assert_eq!(
+        client.try_commit_result(
+            &second,
+            &request_id,
+            &commitment(&env, &second, &fulfillment, &salt)
+        ),
+        Err(Ok(Error::CommitWindowClosed))
+    );
+    client.reveal_result(&first, &fulfillment, &salt, &signature);
+    assert_eq!(
+        client.try_reveal_result(&first, &fulfillment, &salt, &signature),
+        Err(Ok(Error::AlreadyRevealed))
+    );
+    // Not every committee member has revealed yet.
+    assert_eq!(
+        client.try_finalize_round(&request_id),
+        Err(Ok(Error::RoundInProgress))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 201);
+    assert_eq!(client.finalize_round(&request_id), RequestState::Verified);
+}
+
+/// Once members committed, the requester cannot cancel a round whose
+/// reveals it may already have seen.
+#[test]
+fn test_committed_round_cannot_be_cancelled() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, [first, ..], request_id) = setup_round(&env);
+
+    let (fulfillment, salt, signature) = commit_vote(&env, &client, &first, request_id, false);
+    env.ledger().with_mut(|li| li.sequence_number = 121);
+    client.reveal_result(&first, &fulfillment, &salt, &signature);
+    assert_eq!(
+        client.try_cancel_request(&request_id),
+        Err(Ok(Error::RoundInProgress))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 201);
+    assert_eq!(client.finalize_round(&request_id), RequestState::Rejected);
+}
+
+#[test]
+fn test_reveal_must_match_commitment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, [first, second, _], request_id) = setup_round(&env);
+
+    let (fulfillment, salt, signature) = commit_vote(&env, &client, &first, request_id, true);
+    env.ledger().with_mut(|li| li.sequence_number = 121);
+
+    let other_salt = BytesN::from_array(&env, &[7; 32]);
+    assert_eq!(
+        client.try_reveal_result(&first, &fulfillment, &other_salt, &signature),
+        Err(Ok(Error::CommitmentMismatch))
+    );
+    let (flipped, flipped_signature) = signed_fulfillment(&env, request_id, false);
+    assert_eq!(
+        client.try_reveal_result(&first, &flipped, &salt, &flipped_signature),
+        Err(Ok(Error::CommitmentMismatch))
+    );
+    // Copying another provider's reveal does not match a missing commitment.
+    assert_eq!(
+        client.try_reveal_result(&second, &fulfillment, &salt, &signature),
+        Err(Ok(Error::CommitmentMismatch))
+    );
+}
+
+/// A member cannot copy another member's commitment and then replay its reveal.
+#[test]
+fn test_commitments_cannot_be_copied() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, [first, second, _], request_id) = setup_round(&env);
+
+    let (fulfillment, salt, signature) = commit_vote(&env, &client, &first, request_id, true);
+    let copied = client
+        .get_round(&request_id)
+        .commitments
+        .get(0)
+        .unwrap()
+        .hash;
+    assert_eq!(
+        client.try_commit_result(&second, &request_id, &copied),
+        Err(Ok(Error::DuplicateCommitment))
+    );
+
+    // Even with a commitment of its own, replaying the first member's reveal
+    // does not match it, since the provider is part of the preimage.
+    commit_vote(&env, &client, &second, request_id, false);
+    env.ledger().with_mut(|li| li.sequence_number = 121);
+    client.reveal_result(&first, &fulfillment, &salt, &signature);
+    assert_eq!(
+        client.try_reveal_result(&second, &fulfillment, &salt, &signature),
+        Err(Ok(Error::CommitmentMismatch))
+    );
+    assert_eq!(client.get_round(&request_id).reveals.len(), 1);
+}
+
+/// Only majority revealers are paid; committing without revealing halves
+/// a provider's weight.
+#[test]
+fn test_finalize_round_pays_majority_and_penalizes_non_revealers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, [first, second, third], _) = setup_round(&env);
+    let requester = Address::generate(&env);
+    let token = setup_fee(&env, &client, &requester);
+    let request_id = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 2),
+        &Job::ContentIntegrity,
+        &None,
+    );
+
+    let first_vote = commit_vote(&env, &client, &first, request_id, false);
+    commit_vote(&env, &client, &second, request_id, true);
+    let third_vote = commit_vote(&env, &client, &third, request_id, false);
+
+    env.ledger().with_mut(|li| li.sequence_number = 121);
+    client.reveal_result(&first, &first_vote.0, &first_vote.1, &first_vote.2);
+    client.reveal_result(&third, &third_vote.0, &third_vote.1, &third_vote.2);
+
+    env.ledger().with_mut(|li| li.sequence_number = 201);
+    assert_eq!(client.finalize_round(&request_id), RequestState::Rejected);
+    assert!(last_events(&env).contains("request_rejected"));
+
+    assert_eq!(token.balance(&first), 50);
+    assert_eq!(token.balance(&third), 50);
+    assert_eq!(token.balance(&second), 0);
+    assert_eq!(token.balance(&requester), 900);
+    assert_eq!(client.get_provider_weight(&first), 100);
+    assert_eq!(client.get_provider_weight(&second), 50);
+}
+
+/// A round nobody revealed expires and refunds the requester.
+#[test]
+fn test_unrevealed_round_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, [first, second, third], _) = setup_round(&env);
+    let requester = Address::generate(&env);
+    let token = setup_fee(&env, &client, &requester);
+    let request_id = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 2),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    commit_vote(&env, &client, &first, request_id, true);
+
+    env.ledger().with_mut(|li| li.sequence_number = 201);
+    assert_eq!(client.finalize_round(&request_id), RequestState::Expired);
+    assert_eq!(token.balance(&requester), 1_000);
+    // Members that never committed are penalized just like silent committers.
+    assert_eq!(client.get_provider_weight(&first), 50);
+    assert_eq!(client.get_provider_weight(&second), 50);
+    assert_eq!(client.get_provider_weight(&third), 50);
+    assert_eq!(
+        archived_request(&client, request_id).state,
+        RequestState::Expired
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Callbacks
+// ---------------------------------------------------------------------------
+
+#[soroban_sdk::contract]
+pub struct ConsumerMock;
+
+#[soroban_sdk::contractimpl]
+impl ConsumerMock {
+    pub fn on_result(env: Env, request_id: u64, state: RequestState, certificate_id: Option<u64>) {
+        env.storage()
+            .instance()
+            .set(&request_id, &(state, certificate_id));
+    }
+
+    pub fn on_result_broken(
+        _env: Env,
+        _request_id: u64,
+        _state: RequestState,
+        _certificate_id: Option<u64>,
+    ) {
+        panic!("consumer unavailable");
+    }
+
+    pub fn get_result(env: Env, request_id: u64) -> Option<(RequestState, Option<u64>)> {
+        env.storage().instance().get(&request_id)
+    }
+}
+
+fn callback(env: &Env, consumer: &Address, function: &str) -> Option<Callback> {
+    Some(Callback {
+        contract: consumer.clone(),
+        function: Symbol::new(env, function),
+    })
+}
+
+/// Verified requests mint a certificate for the requester and report it to
+/// the callback.
+#[test]
+fn test_callback_receives_verified_result() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    let consumer = env.register(ConsumerMock, ());
+    let consumer_client = ConsumerMockClient::new(&env, &consumer);
+
+    let requester = Address::generate(&env);
+    let request_id = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &callback(&env, &consumer, "on_result"),
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+
+    assert_eq!(
+        consumer_client.get_result(&request_id),
+        Some((RequestState::Verified, Some(1)))
+    );
+    let record = archived_request(&client, request_id);
+    assert_eq!(record.certificate_id, Some(1));
+    assert_eq!(record.callback_status, CallbackStatus::Succeeded);
+
+    let provenance: Address = env.as_contract(&client.address, || {
+        env.storage().instance().get(&DataKey::Provenance).unwrap()
+    });
+    let (owner, details) = ProvenanceMockClient::new(&env, &provenance)
+        .get_mint(&1)
+        .unwrap();
+    assert_eq!(owner, requester);
+    assert_eq!(details.manifest_hash.len(), 64);
+    assert_eq!(details.hash_algorithm, HashAlgorithm::Sha256);
+}
+
+/// Rejected and cancelled requests are reported without a certificate.
+#[test]
+fn test_callback_receives_unverified_results() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    let consumer = env.register(ConsumerMock, ());
+    let consumer_client = ConsumerMockClient::new(&env, &consumer);
+    let on_result = callback(&env, &consumer, "on_result");
+
+    let rejected = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &on_result,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, rejected, false);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+    assert_eq!(
+        consumer_client.get_result(&rejected),
+        Some((RequestState::Rejected, None))
+    );
+
+    let cancelled = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 2),
+        &Job::ContentIntegrity,
+        &on_result,
+    );
+    client.cancel_request(&cancelled);
+    assert_eq!(
+        consumer_client.get_result(&cancelled),
+        Some((RequestState::Cancelled, None))
+    );
+    assert_eq!(
+        archived_request(&client, cancelled).callback_status,
+        CallbackStatus::Succeeded
+    );
+}
+
+/// A failing or missing callback is recorded but does not block finalization.
+#[test]
+fn test_failed_callback_does_not_block_fulfillment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+    let consumer = env.register(ConsumerMock, ());
+
+    for (fill, function) in [(1, "on_result_broken"), (2, "no_such_function")] {
+        let request_id = client.submit_request(
+            &Address::generate(&env),
+            &sha256_hash(&env, fill),
+            &Job::ContentIntegrity,
+            &callback(&env, &consumer, function),
+        );
+        let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+        assert_eq!(
+            client.fulfill_request(&provider, &fulfillment, &signature),
+            RequestState::Verified
+        );
+        let record = archived_request(&client, request_id);
+        assert_eq!(record.state, RequestState::Verified);
+        assert_eq!(record.callback_status, CallbackStatus::Failed);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Certificates
+// ---------------------------------------------------------------------------
+
+/// Against the real provenance contract, the oracle mints once the
+/// provenance admin authorizes it, alongside other minters such as the
+/// registry.
+#[test]
+fn test_mints_through_provenance_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let provenance_id = env.register(::provenance::ProvenanceContract, ());
+    let provenance = ::provenance::ProvenanceContractClient::new(&env, &provenance_id);
+    provenance.initialize(&Address::generate(&env));
+    client.init(
+        &env.register(RegistryMock, ()),
+        &provenance_id,
+        &Address::generate(&env),
+    );
+    let provider = Address::generate(&env);
+    client.add_provider(&provider);
+
+    // Until it is authorized, verified requests get no certificate.
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.fulfill_request(&provider, &fulfillment, &signature),
+        RequestState::Verified
+    );
+    assert_eq!(archived_request(&client, request_id).certificate_id, None);
+
+    let registry = Address::generate(&env);
+    provenance.add_minter(&registry);
+    provenance.add_minter(&contract_id);
+    let details = ::provenance::CertificateDetails {
+        storage_id: String::from_str(&env, "sid"),
+        manifest_hash: String::from_str(&env, "registry-content"),
+        attestation_hash: String::from_str(&env, ""),
+        hash_algorithm: ::provenance::HashAlgorithm::Sha256,
+        layout: ::provenance::ContentLayout::Whole,
+        job: ::provenance::JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
+    };
+    let registry_certificate = provenance.mint(&registry, &Address::generate(&env), &details);
+
+    let requester = Address::generate(&env);
+    let request_id = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 2),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+    let certificate_id = archived_request(&client, request_id)
+        .certificate_id
+        .unwrap();
+    assert_ne!(certificate_id, registry_certificate);
+    let certificate = provenance.get_certificate(&certificate_id);
+    assert_eq!(certificate.creator, requester);
+    assert_eq!(certificate.job_digest, Job::ContentIntegrity.digest(&env));
+}
+
+// ---------------------------------------------------------------------------
+// Batched fulfillment
+// ---------------------------------------------------------------------------
+
+fn batch_leaf_hash(env: &Env, leaf: &BatchLeaf) -> [u8; 32] {
+    let mut data = Bytes::from_array(env, &[0x00]);
+    data.append(&leaf.clone().to_xdr(env));
+    env.crypto().sha256(&data).to_array()
+}
+
+fn batch_node_hash(env: &Env, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut data = Bytes::from_array(env, &[0x01]);
+    data.append(&Bytes::from_array(env, left));
+    data.append(&Bytes::from_array(env, right));
+    env.crypto().sha256(&data).to_array()
+}
+
+/// Builds the batch tree over `leaves`, signs its root with the TEE key the
+/// registry mock trusts and returns a proof for every leaf.
+fn signed_batch(
+    env: &Env,
+    leaves: &[BatchLeaf],
+    tee_hash: [u8; 32],
+) -> (FulfillmentBatch, BytesN<64>, std::vec::Vec<BatchProof>) {
+    let mut levels = std::vec![leaves
+        .iter()
+        .map(|leaf| batch_leaf_hash(env, leaf))
+        .collect::<std::vec::Vec<_>>()];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => batch_node_hash(env, left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+
+    let proofs = (0..leaves.len())
+        .map(|index| {
+            let mut siblings = soroban_sdk::Vec::new(env);
+            let mut i = index;
+            for level in &levels[..levels.len() - 1] {
+                let sibling = if i % 2 == 1 {
+                    Some(level[i - 1])
+                } else {
+                    level.get(i + 1).copied()
+                };
+                if let Some(sibling) = sibling {
+                    siblings.push_back(BytesN::from_array(env, &sibling));
+                }
+                i /= 2;
+            }
+            BatchProof {
+                index: index as u32,
+                leaf: leaves[index].clone(),
+                siblings,
+            }
+        })
+        .collect();
+
+    let (signing_key, signer) = create_keypair(env, 1);
+    let batch = FulfillmentBatch {
+        root: BytesN::from_array(env, &levels.last().unwrap()[0]),
+        leaf_count: leaves.len() as u32,
+        signer,
+        tee_hash: BytesN::from_array(env, &tee_hash),
+    };
+    let payload = batch.clone().to_xdr(env);
+    let mut buf = std::vec![0u8; payload.len() as usize];
+    payload.copy_into_slice(&mut buf);
+    let signature = sign_payload(env, &signing_key, &buf);
+    (batch, signature, proofs)
+}
+
+fn proof_vec(env: &Env, proofs: &[BatchProof]) -> soroban_sdk::Vec<BatchProof> {
+    let mut out = soroban_sdk::Vec::new(env);
+    for proof in proofs {
+        out.push_back(proof.clone());
+    }
+    out
+}
+
+#[test]
+fn test_fulfill_batch_finalizes_each_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    let leaves: std::vec::Vec<BatchLeaf> = (1..=5u8)
+        .map(|fill| BatchLeaf {
+            request_id: client.submit_request(
+                &Address::generate(&env),
+                &sha256_hash(&env, fill),
+                &Job::ContentIntegrity,
+                &None,
+            ),
+            verified: fill % 2 == 1,
+            result: JobResult::ContentIntegrity,
+        })
+        .collect();
+    let (batch, signature, proofs) = signed_batch(&env, &leaves, [88; 32]);
+
+    let outcomes = client.fulfill_batch(&provider, &batch, &signature, &proof_vec(&env, &proofs));
+    let attestation_hash: BytesN<32> = env.crypto().sha256(&batch.to_xdr(&env)).into();
+    assert_eq!(outcomes.len(), 5);
+    for (leaf, outcome) in leaves.iter().zip(outcomes.iter()) {
+        let state = if leaf.verified {
+            RequestState::Verified
+        } else {
+            RequestState::Rejected
+        };
+        assert_eq!(outcome, BatchOutcome::Finalized(state.clone()));
+        let record = archived_request(&client, leaf.request_id);
+        assert_eq!(record.state, state);
+        assert_eq!(record.provider, Some(provider.clone()));
+        assert_eq!(record.attestation_hash, Some(attestation_hash.clone()));
+    }
+}
+
+/// Bad proofs and unfulfillable requests are skipped; the rest of the batch
+/// still goes through.
+#[test]
+fn test_fulfill_batch_skips_invalid_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    let first = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let second = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 2),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let cancelled = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 3),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    client.cancel_request(&cancelled);
+    let leaves = [
+        BatchLeaf {
+            request_id: first,
+            verified: true,
+            result: JobResult::ContentIntegrity,
+        },
+        BatchLeaf {
+            request_id: second,
+            verified: true,
+            result: JobResult::ContentIntegrity,
+        },
+        BatchLeaf {
+            request_id: cancelled,
+            verified: true,
+            result: JobResult::ContentIntegrity,
+        },
+    ];
+    let (batch, signature, proofs) = signed_batch(&env, &leaves, [88; 32]);
+
+    // Flipping a result breaks its proof.
+    let mut tampered = proofs[1].clone();
+    tampered.leaf.verified = false;
+    let mut out_of_range = proofs[0].clone();
+    out_of_range.index = 3;
+
+    let outcomes = client.fulfill_batch(
+        &provider,
+        &batch,
+        &signature,
+        &proof_vec(
+            &env,
+            &[
+                tampered,
+                out_of_range,
+                proofs[0].clone(),
+                proofs[0].clone(),
+                proofs[2].clone(),
+            ],
+        ),
+    );
+    assert_eq!(
+        outcomes,
+        soroban_sdk::vec![
+            &env,
+            BatchOutcome::InvalidProof,
+            BatchOutcome::InvalidProof,
+            BatchOutcome::Finalized(RequestState::Verified),
+            BatchOutcome::Refused(Error::AlreadyFinalized as u32),
+            BatchOutcome::Refused(Error::AlreadyFinalized as u32),
+        ]
+    );
+    assert_eq!(live_request(&client, second).state, RequestState::Pending);
+}
+
+#[test]
+fn test_fulfill_batch_requires_trusted_signer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let leaves = [BatchLeaf {
+        request_id,
+        verified: true,
+        result: JobResult::ContentIntegrity,
+    }];
+    let (batch, signature, proofs) = signed_batch(&env, &leaves, [1; 32]);
+    assert_eq!(
+        client.try_fulfill_batch(&provider, &batch, &signature, &proof_vec(&env, &proofs)),
+        Err(Ok(Error::UnauthorizedSigner))
+    );
+    assert_eq!(
+        client.try_fulfill_batch(
+            &Address::generate(&env),
+            &batch,
+            &signature,
+            &proof_vec(&env, &proofs)
+        ),
+        Err(Ok(Error::UnauthorizedProvider))
+    );
+    assert_eq!(
+        live_request(&client, request_id).state,
+        RequestState::Pending
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Deduplication
+// ---------------------------------------------------------------------------
+
+/// Resubmitting pending content fails without charging another fee, and
+/// `find_request` points at the pending request, which has no certificate
+/// yet.
+#[test]
+fn test_resubmitting_pending_content_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _provider) = setup_fulfillment(&env);
+    let requester = Address::generate(&env);
+    let token = setup_fee(&env, &client, &requester);
+
+    let request_id = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    assert_eq!(
+        client.try_submit_request(
+            &requester,
+            &sha256_hash(&env, 1),
+            &Job::ContentIntegrity,
+            &None
+        ),
+        Err(Ok(Error::AlreadyRequested))
+    );
+    assert_eq!(
+        client.find_request(&sha256_hash(&env, 1), &Job::ContentIntegrity),
+        Some(ExistingRequest {
+            request_id,
+            certificate_id: None,
+        })
+    );
+    assert_eq!(token.balance(&requester), 900);
+    assert_eq!(pending_ids(&client, 0, 10), [request_id]);
+
+    // The index is dropped once the request goes stale.
+    env.ledger().with_mut(|li| li.sequence_number += 101);
+    assert_eq!(
+        client.find_request(&sha256_hash(&env, 1), &Job::ContentIntegrity),
+        None
+    );
+    assert_eq!(
+        client.submit_request(
+            &requester,
+            &sha256_hash(&env, 1),
+            &Job::ContentIntegrity,
+            &None
+        ),
+        request_id + 1
+    );
+}
+
+/// Certified content cannot be resubmitted and resolves to the request
+/// that holds its certificate.
+#[test]
+fn test_resubmitting_certified_content_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+
+    env.ledger().with_mut(|li| li.sequence_number += 10_000);
+    assert_eq!(
+        client.try_submit_request(
+            &Address::generate(&env),
+            &sha256_hash(&env, 1),
+            &Job::ContentIntegrity,
+            &None,
+        ),
+        Err(Ok(Error::AlreadyRequested))
+    );
+    let existing = client
+        .find_request(&sha256_hash(&env, 1), &Job::ContentIntegrity)
+        .unwrap();
+    assert_eq!(existing.request_id, request_id);
+    assert_eq!(existing.certificate_id, Some(1));
+    assert_eq!(
+        archived_request(&client, request_id).certificate_id,
+        existing.certificate_id
+    );
+}
+
+/// A second requester never ends up sharing a request the first requester
+/// controls: once the first requester cancels, the second can submit their
+/// own request and callback.
+#[test]
+fn test_original_requester_cancelling_frees_content() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _provider) = setup_fulfillment(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let callback = Callback {
+        contract: Address::generate(&env),
+        function: Symbol::new(&env, "on_verified"),
+    };
+
+    let request_id =
+        client.submit_request(&first, &sha256_hash(&env, 1), &Job::ContentIntegrity, &None);
+    assert_eq!(
+        client.try_submit_request(
+            &second,
+            &sha256_hash(&env, 1),
+            &Job::ContentIntegrity,
+            &Some(callback.clone()),
+        ),
+        Err(Ok(Error::AlreadyRequested))
+    );
+
+    client.cancel_request(&request_id);
+    assert_eq!(
+        client.find_request(&sha256_hash(&env, 1), &Job::ContentIntegrity),
+        None
+    );
+    let own = client.submit_request(
+        &second,
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &Some(callback),
+    );
+    assert_ne!(own, request_id);
+    let request = live_request(&client, own);
+    assert_eq!(request.requester, second);
+    assert_eq!(request.state, RequestState::Pending);
+}
+
+/// Content whose request was rejected or cancelled can be submitted again.
+#[test]
+fn test_unfinished_content_can_be_resubmitted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    let rejected = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, rejected, false);
+    client.fulfill_request(&provider, &fulfillment, &signature);
+    let retried = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    assert_ne!(retried, rejected);
+
+    client.cancel_request(&retried);
+    let again = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    assert_ne!(again, retried);
+    assert_eq!(live_request(&client, again).state, RequestState::Pending);
+}
+
+// ---------------------------------------------------------------------------
+// Job types
+// ---------------------------------------------------------------------------
+
+fn ai_detection_job() -> Job {
+    Job::AiGenerationDetection(AiDetectionParams {
+        threshold_bps: 5_000,
+    })
+}
+
+/// A verified typed result is recorded on the certificate.
+#[test]
+fn test_job_result_is_recorded_on_certificate() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &ai_detection_job(),
+        &None,
+    );
+    assert_eq!(
+        client.next_pending(&0, &10).requests.get(0).unwrap().job,
+        ai_detection_job()
+    );
+
+    let result = JobResult::AiGenerationDetection(AiDetectionResult {
+        score_bps: 730,
+        model: String::from_str(&env, "detector-v2"),
+    });
+    let (fulfillment, signature) = signed_result(&env, request_id, true, result.clone());
+    assert_eq!(
+        client.fulfill_request(&provider, &fulfillment, &signature),
+        RequestState::Verified
+    );
+
+    let provenance: Address = env.as_contract(&client.address, || {
+        env.storage().instance().get(&DataKey::Provenance).unwrap()
+    });
+    let (_, details) = ProvenanceMockClient::new(&env, &provenance)
+        .get_mint(&1)
+        .unwrap();
+    assert_eq!(details.job, result);
+    assert_eq!(details.job_digest, ai_detection_job().digest(&env));
+}
+
+/// Results for another job type are refused, alone or in a batch.
+#[test]
+fn test_result_must_match_job_type() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &ai_detection_job(),
+        &None,
+    );
+    let (fulfillment, signature) = signed_fulfillment(&env, request_id, true);
+    assert_eq!(
+        client.try_fulfill_request(&provider, &fulfillment, &signature),
+        Err(Ok(Error::JobMismatch))
+    );
+
+    let leaves = [BatchLeaf {
+        request_id,
+        verified: true,
+        result: JobResult::ContentIntegrity,
+    }];
+    let (batch, signature, proofs) = signed_batch(&env, &leaves, [88; 32]);
+    assert_eq!(
+        client.fulfill_batch(&provider, &batch, &signature, &proof_vec(&env, &proofs)),
+        soroban_sdk::vec![&env, BatchOutcome::Refused(Error::JobMismatch as u32)]
+    );
+    assert_eq!(
+        live_request(&client, request_id).state,
+        RequestState::Pending
+    );
+}
+
+/// Verified results must satisfy the job's parameters; results failing
+/// them can only reject the content.
+#[test]
+fn test_verified_result_must_satisfy_job_parameters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, provider) = setup_fulfillment(&env);
+
+    let detection = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 1),
+        &ai_detection_job(),
+        &None,
+    );
+    let likely_generated = JobResult::AiGenerationDetection(AiDetectionResult {
+        score_bps: 5_000,
+        model: String::from_str(&env, "detector-v2"),
+    });
+    let (fulfillment, signature) = signed_result(&env, detection, true, likely_generated.clone());
+    assert_eq!(
+        client.try_fulfill_request(&provider, &fulfillment, &signature),
+        Err(Ok(Error::ResultContradictsJob))
+    );
+    let leaves = [BatchLeaf {
+        request_id: detection,
+        verified: true,
+        result: likely_generated.clone(),
+    }];
+    let (batch, signature, proofs) = signed_batch(&env, &leaves, [88; 32]);
+    assert_eq!(
+        client.fulfill_batch(&provider, &batch, &signature, &proof_vec(&env, &proofs)),
+        soroban_sdk::vec![
+            &env,
+            BatchOutcome::Refused(Error::ResultContradictsJob as u32)
+        ]
+    );
+    let (fulfillment, signature) = signed_result(&env, detection, false, likely_generated);
+    assert_eq!(
+        client.fulfill_request(&provider, &fulfillment, &signature),
+        RequestState::Rejected
+    );
+
+    let signature_job = Job::ManifestSignature(ManifestSignatureParams {
+        manifest_uri: String::from_str(&env, "ipfs://manifest"),
+        expected_signer: Some(String::from_str(&env, "CN=Newsroom")),
+    });
+    let signed_by = |signer: &str| {
+        JobResult::ManifestSignature(ManifestSignatureResult {
+            signer: String::from_str(&env, signer),
+        })
+    };
+    let request_id = client.submit_request(
+        &Address::generate(&env),
+        &sha256_hash(&env, 2),
+        &signature_job,
+        &None,
+    );
+    let (fulfillment, signature) = signed_result(&env, request_id, true, signed_by("CN=Impostor"));
+    assert_eq!(
+        client.try_fulfill_request(&provider, &fulfillment, &signature),
+        Err(Ok(Error::ResultContradictsJob))
+    );
+    let (fulfillment, signature) = signed_result(&env, request_id, true, signed_by("CN=Newsroom"));
+    assert_eq!(
+        client.fulfill_request(&provider, &fulfillment, &signature),
+        RequestState::Verified
+    );
+}
+
+/// The same content can be submitted once per job and set of parameters.
+#[test]
+fn test_deduplication_is_per_job() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _provider) = setup_fulfillment(&env);
+    let requester = Address::generate(&env);
+
+    let integrity = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 1),
+        &Job::ContentIntegrity,
+        &None,
+    );
+    let detection = client.submit_request(
+        &requester,
+        &sha256_hash(&env, 1),
+        &ai_detection_job(),
+        &None,
+    );
+    assert_ne!(integrity, detection);
+    assert_eq!(
+        client.try_submit_request(
+            &requester,
+            &sha256_hash(&env, 1),
+            &ai_detection_job(),
+            &None
+        ),
+        Err(Ok(Error::AlreadyRequested))
+    );
+    assert_eq!(
+        client
+            .find_request(&sha256_hash(&env, 1), &ai_detection_job())
+            .map(|existing| existing.request_id),
+        Some(detection)
+    );
+
+    let stricter = Job::AiGenerationDetection(AiDetectionParams {
+        threshold_bps: 1_000,
+    });
+    let strict_detection =
+        client.submit_request(&requester, &sha256_hash(&env, 1), &stricter, &None);
+    assert_ne!(strict_detection, detection);
+    assert_eq!(
+        client
+            .find_request(&sha256_hash(&env, 1), &stricter)
+            .map(|existing| existing.request_id),
+        Some(strict_detection)
+    );
+}
@@ -59,18 +59,25 @@ echo $CONTRACT_ID > .contract-id
 echo -e "Contract ID saved to .contract-id\n"
 
 echo -e "${YELLOW}Step 5: Initializing contract...${NC}"
-# For now, we'll use the deployer as the oracle address
-# In production, this should be the actual oracle contract address
-ORACLE_ADDRESS=$DEPLOYER_ADDRESS
+# For now, we'll use the deployer as the admin and minter address
+# In production, the minters should be the registry and oracle contract addresses
+MINTER_ADDRESS=$DEPLOYER_ADDRESS
 
 stellar contract invoke \
     --id $CONTRACT_ID \
     --source $IDENTITY \
     --network $NETWORK \
     -- initialize \
-    --oracle $ORACLE_ADDRESS
+    --admin $DEPLOYER_ADDRESS
 
-echo -e "${GREEN}✓ Contract initialized with oracle: $ORACLE_ADDRESS${NC}\n"
+stellar contract invoke \
+    --id $CONTRACT_ID \
+    --source $IDENTITY \
+    --network $NETWORK \
+    -- add_minter \
+    --minter $MINTER_ADDRESS
+
+echo -e "${GREEN}✓ Contract initialized with minter: $MINTER_ADDRESS${NC}\n"
 
 echo -e "${YELLOW}Step 6: Testing mint function...${NC}"
 # Create a test recipient address
@@ -82,6 +89,7 @@ CERT_ID=$(stellar contract invoke \
     --source $IDENTITY \
     --network $NETWORK \
     -- mint \
+    --minter $MINTER_ADDRESS \
     --to $RECIPIENT_ADDRESS \
     --details '{"content_hash":"test_hash_123","metadata":"test deployment certificate"}')
 
@@ -1,7 +1,7 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, symbol_short, Address, Env,
-    String,
+    contract, contracterror, contractevent, contractimpl, contracttype, panic_with_error,
+    symbol_short, Address, BytesN, Env, String,
 };
 
 #[contractevent]
@@ -15,6 +15,22 @@ pub struct CertificateMinted {
     pub manifest_hash: String,
 }
 
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateFlagged {
+    #[topic]
+    pub certificate_id: u64,
+    pub reason: String,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateRevoked {
+    #[topic]
+    pub certificate_id: u64,
+    pub reason: String,
+}
+
 #[contract]
 pub struct ProvenanceContract;
 
@@ -23,6 +39,101 @@ pub struct ProvenanceContract;
 #[repr(u32)]
 pub enum ProvenanceError {
     CertificateNotFound = 1,
+    /// The caller is not an authorized minter.
+    UnauthorizedMinter = 2,
+}
+
+/// Algorithm that produced a manifest digest, identified by its multihash code
+/// so verifiers know how to recompute it.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum HashAlgorithm {
+    Sha256 = 0x12,
+    Keccak256 = 0x1b,
+    Blake3 = 0x1e,
+    Blake2b256 = 0xb220,
+}
+
+/// Merkle root over fixed-size chunks of large content, with the chunk
+/// parameters needed to recompute it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleCommitment {
+    pub root: BytesN<32>,
+    pub chunk_size: u32,
+    pub chunk_count: u32,
+}
+
+/// How the certified content was committed to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ContentLayout {
+    /// `manifest_hash` is a digest of the whole content.
+    Whole,
+    /// `manifest_hash` is the hex-encoded Merkle root of the chunk commitment.
+    Chunked(MerkleCommitment),
+}
+
+/// Kind of verification a certificate attests to.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum JobType {
+    ContentIntegrity = 0,
+    ManifestSignature = 1,
+    C2paValidation = 2,
+    AiGenerationDetection = 3,
+}
+
+/// Findings of a manifest signature check.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ManifestSignatureResult {
+    /// Subject of the certificate that signed the manifest.
+    pub signer: String,
+}
+
+/// Findings of a C2PA manifest store validation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct C2paResult {
+    /// Claim generator recorded in the active manifest.
+    pub claim_generator: String,
+    /// SHA-256 of the active manifest.
+    pub active_manifest: BytesN<32>,
+}
+
+/// Findings of an AI-generation detector.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AiDetectionResult {
+    /// Likelihood that the content is AI-generated, in basis points.
+    pub score_bps: u32,
+    /// Detector model that produced the score.
+    pub model: String,
+}
+
+/// Job-specific findings of the verification a certificate attests to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JobResult {
+    /// The content matches `manifest_hash`; nothing further to record.
+    ContentIntegrity,
+    ManifestSignature(ManifestSignatureResult),
+    C2paValidation(C2paResult),
+    AiGenerationDetection(AiDetectionResult),
+}
+
+impl JobResult {
+    pub fn job_type(&self) -> JobType {
+        match self {
+            JobResult::ContentIntegrity => JobType::ContentIntegrity,
+            JobResult::ManifestSignature(_) => JobType::ManifestSignature,
+            JobResult::C2paValidation(_) => JobType::C2paValidation,
+            JobResult::AiGenerationDetection(_) => JobType::AiGenerationDetection,
+        }
+    }
 }
 
 #[contracttype]
@@ -31,6 +142,10 @@ pub struct CertificateDetails {
     pub storage_id: String,
     pub manifest_hash: String,
     pub attestation_hash: String,
+    pub hash_algorithm: HashAlgorithm,
+    pub layout: ContentLayout,
+    pub job: JobResult,
+    pub job_digest: BytesN<32>,
 }
 
 #[contracttype]
@@ -39,52 +154,96 @@ pub struct Certificate {
     pub storage_id: String,
     pub manifest_hash: String,
     pub attestation_hash: String,
+    /// Algorithm that produced `manifest_hash`.
+    pub hash_algorithm: HashAlgorithm,
+    /// Whether `manifest_hash` covers the whole content or is a chunk root.
+    pub layout: ContentLayout,
+    /// Kind of verification performed and its findings.
+    pub job: JobResult,
+    /// SHA-256 identifying the job with the parameters it ran under; all
+    /// zeroes for jobs without parameters.
+    pub job_digest: BytesN<32>,
     pub creator: Address,
     /// Ledger timestamp at mint time; set once and immutable (no update API).
     pub timestamp: u64,
 }
 
+/// Suspect marker attached to a certificate after issuance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateFlag {
+    pub reason: String,
+    /// Ledger timestamp at which the certificate was flagged.
+    pub flagged_at: u64,
+}
+
 #[contractimpl]
 impl ProvenanceContract {
-    /// Initialize the contract with the Oracle address
+    /// Initialize the contract with its admin
     ///
     /// # Arguments
-    /// * `oracle` - Address of the Oracle contract authorized to mint certificates
-    pub fn initialize(env: Env, oracle: Address) {
+    /// * `admin` - Address allowed to authorize minters, e.g. the Registry and
+    ///   Oracle contracts
+    pub fn initialize(env: Env, admin: Address) {
         // Ensure not already initialized
-        if env.storage().persistent().has(&symbol_short!("ORACLE")) {
+        if env.storage().persistent().has(&symbol_short!("ADMIN")) {
             panic!("Contract already initialized");
         }
 
-        // Store oracle address
+        // Store admin address
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("ADMIN"), &admin);
+    }
+
+    /// Authorize `minter` to mint, flag and revoke certificates.
+    /// Only callable by the admin.
+    pub fn add_minter(env: Env, minter: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("MINTER"), minter), &true);
+    }
+
+    /// Withdraw `minter`'s authorization. Certificates it issued are kept.
+    /// Only callable by the admin.
+    pub fn remove_minter(env: Env, minter: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .persistent()
+            .remove(&(symbol_short!("MINTER"), minter));
+    }
+
+    /// Return whether `minter` is authorized to mint certificates.
+    pub fn is_minter(env: Env, minter: Address) -> bool {
         env.storage()
             .persistent()
-            .set(&symbol_short!("ORACLE"), &oracle);
+            .has(&(symbol_short!("MINTER"), minter))
     }
 
     /// Mint a new provenance certificate for verified content.
-    /// Only callable by the Oracle contract.
+    /// Only callable by an authorized minter.
     ///
     /// # Arguments
+    /// * `minter` - Authorized minter issuing the certificate; must authorize the call
     /// * `to` - Address of the certificate recipient/owner (stored as `creator`)
-    /// * `details` - Certificate details: storage_id, manifest_hash, attestation_hash
+    /// * `details` - Certificate details: storage_id, manifest_hash, attestation_hash,
+    ///   hash_algorithm, content layout, job findings and job digest
     ///
     /// # Returns
     /// Certificate ID on success. Emits `CertificateMinted` event.
-    pub fn mint(env: Env, to: Address, details: CertificateDetails) -> u64 {
-        // Get oracle address from storage
-        let oracle: Address = env
-            .storage()
-            .persistent()
-            .get(&symbol_short!("ORACLE"))
-            .expect("Contract not initialized");
+    pub fn mint(env: Env, minter: Address, to: Address, details: CertificateDetails) -> u64 {
+        Self::require_minter(&env, &minter);
 
-        // Require authorization from the oracle contract only
-        oracle.require_auth();
-
-        // Prevent duplicate certificates for the same manifest hash by maintaining
-        // a manifest_hash -> certificate_id mapping in storage.
-        let manifest_key = (symbol_short!("MANI"), details.manifest_hash.clone());
+        // Prevent duplicate certificates for the same manifest hash and job
+        // by maintaining a (manifest_hash, job type, job digest) -> certificate_id
+        // mapping in storage.
+        let manifest_key = (
+            symbol_short!("MANI"),
+            details.manifest_hash.clone(),
+            details.job.job_type(),
+            details.job_digest.clone(),
+        );
         if env.storage().persistent().has(&manifest_key) {
             panic!("Certificate already exists for this manifest hash");
         }
@@ -101,6 +260,10 @@ impl ProvenanceContract {
             storage_id: details.storage_id.clone(),
             manifest_hash: details.manifest_hash.clone(),
             attestation_hash: details.attestation_hash.clone(),
+            hash_algorithm: details.hash_algorithm,
+            layout: details.layout.clone(),
+            job: details.job.clone(),
+            job_digest: details.job_digest.clone(),
             creator: to.clone(),
             timestamp: mint_timestamp,
         };
@@ -139,6 +302,121 @@ impl ProvenanceContract {
             .get(&cert_key)
             .ok_or(ProvenanceError::CertificateNotFound)
     }
+
+    /// Mark a certificate as suspect, e.g. because the attesting enclave key
+    /// was declared compromised before the certificate was issued.
+    /// Only callable by an authorized minter.
+    ///
+    /// The certificate itself is left untouched; the flag is stored alongside
+    /// it and reported by `get_flag`. Emits `CertificateFlagged` event.
+    pub fn flag_certificate(
+        env: Env,
+        minter: Address,
+        certificate_id: u64,
+        reason: String,
+    ) -> Result<(), ProvenanceError> {
+        Self::require_minter(&env, &minter);
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&(symbol_short!("CERT"), certificate_id))
+        {
+            return Err(ProvenanceError::CertificateNotFound);
+        }
+
+        let flag = CertificateFlag {
+            reason: reason.clone(),
+            flagged_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("FLAG"), certificate_id), &flag);
+
+        CertificateFlagged {
+            certificate_id,
+            reason,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Revoke a certificate issued in error, e.g. to an account that minted
+    /// content someone else had a prior claim on.
+    /// Only callable by an authorized minter.
+    ///
+    /// The certificate is flagged with `reason` like `flag_certificate`, and
+    /// its manifest hash is released so a certificate for the same manifest
+    /// hash and job can be minted again. Emits `CertificateRevoked` event.
+    pub fn revoke_certificate(
+        env: Env,
+        minter: Address,
+        certificate_id: u64,
+        reason: String,
+    ) -> Result<(), ProvenanceError> {
+        Self::require_minter(&env, &minter);
+
+        let certificate: Certificate = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("CERT"), certificate_id))
+            .ok_or(ProvenanceError::CertificateNotFound)?;
+
+        let manifest_key = (
+            symbol_short!("MANI"),
+            certificate.manifest_hash,
+            certificate.job.job_type(),
+            certificate.job_digest,
+        );
+        let current: Option<u64> = env.storage().persistent().get(&manifest_key);
+        if current == Some(certificate_id) {
+            env.storage().persistent().remove(&manifest_key);
+        }
+
+        let flag = CertificateFlag {
+            reason: reason.clone(),
+            flagged_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("FLAG"), certificate_id), &flag);
+
+        CertificateRevoked {
+            certificate_id,
+            reason,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the suspect flag recorded for a certificate, if any.
+    pub fn get_flag(env: Env, certificate_id: u64) -> Option<CertificateFlag> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("FLAG"), certificate_id))
+    }
+
+    fn require_admin(env: &Env) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("ADMIN"))
+            .expect("Contract not initialized");
+        admin.require_auth();
+    }
+
+    /// Require `minter`'s authorization and that it is an authorized minter.
+    fn require_minter(env: &Env, minter: &Address) {
+        if !env.storage().persistent().has(&symbol_short!("ADMIN")) {
+            panic!("Contract not initialized");
+        }
+        minter.require_auth();
+        if !Self::is_minter(env.clone(), minter.clone()) {
+            panic_with_error!(env, ProvenanceError::UnauthorizedMinter);
+        }
+    }
 }
 
 mod test;
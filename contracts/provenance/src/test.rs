@@ -2,7 +2,7 @@
 extern crate std;
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, testutils::Events, Address, Env, String};
+use soroban_sdk::{testutils::Address as _, testutils::Events, Address, BytesN, Env, String};
 
 #[test]
 fn test_mint_certificate() {
@@ -16,27 +16,37 @@ fn test_mint_certificate() {
     let owner = Address::generate(&env);
 
     // Initialize contract with oracle address
-    client.initialize(&oracle);
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
 
     let details = CertificateDetails {
         storage_id: String::from_str(&env, "storage_abc123"),
         manifest_hash: String::from_str(&env, "abc123hash"),
         attestation_hash: String::from_str(&env, "attest_abc123"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
     };
 
-    let cert_id = client.mint(&owner, &details);
+    let cert_id = client.mint(&oracle, &owner, &details);
     assert_eq!(cert_id, 1);
 
-    let cert = client.get_certificate(&cert_id);
-    let certificate = client.get_certificate(&cert_id);
+    let certificate = client.try_get_certificate(&cert_id);
     assert!(certificate.is_ok());
 
-    let cert = certificate.unwrap();
+    let cert = client.get_certificate(&cert_id);
     assert_eq!(cert.storage_id, details.storage_id);
     assert_eq!(cert.manifest_hash, details.manifest_hash);
     assert_eq!(cert.attestation_hash, details.attestation_hash);
+    assert_eq!(cert.hash_algorithm, HashAlgorithm::Sha256);
+    assert_eq!(cert.layout, ContentLayout::Whole);
     assert_eq!(cert.creator, owner);
-    assert_eq!(cert.timestamp, env.ledger().timestamp(), "timestamp must be set at mint time");
+    assert_eq!(
+        cert.timestamp,
+        env.ledger().timestamp(),
+        "timestamp must be set at mint time"
+    );
 }
 
 #[test]
@@ -52,40 +62,50 @@ fn test_mint_multiple_certificates() {
     let owner2 = Address::generate(&env);
 
     // Initialize contract
-    client.initialize(&oracle);
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
 
     let details1 = CertificateDetails {
         storage_id: String::from_str(&env, "s1"),
         manifest_hash: String::from_str(&env, "hash1"),
         attestation_hash: String::from_str(&env, "a1"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
     };
     let details2 = CertificateDetails {
         storage_id: String::from_str(&env, "s2"),
         manifest_hash: String::from_str(&env, "hash2"),
         attestation_hash: String::from_str(&env, "a2"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
     };
     let details3 = CertificateDetails {
         storage_id: String::from_str(&env, "s3"),
         manifest_hash: String::from_str(&env, "hash3"),
         attestation_hash: String::from_str(&env, "a3"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
     };
 
     // Mint multiple certificates
-    let cert_id1 = client.mint(&owner1, &details1);
-    let cert_id2 = client.mint(&owner2, &details2);
-    let cert_id3 = client.mint(&owner1, &details3);
+    let cert_id1 = client.mint(&oracle, &owner1, &details1);
+    let cert_id2 = client.mint(&oracle, &owner2, &details2);
+    let cert_id3 = client.mint(&oracle, &owner1, &details3);
 
     assert_eq!(cert_id1, 1);
     assert_eq!(cert_id2, 2);
     assert_eq!(cert_id3, 3);
 
     // Verify all certificates exist
-    client.get_certificate(&cert_id1);
-    client.get_certificate(&cert_id2);
-    client.get_certificate(&cert_id3);
-    assert!(client.get_certificate(&cert_id1).is_ok());
-    assert!(client.get_certificate(&cert_id2).is_ok());
-    assert!(client.get_certificate(&cert_id3).is_ok());
+    assert!(client.try_get_certificate(&cert_id1).is_ok());
+    assert!(client.try_get_certificate(&cert_id2).is_ok());
+    assert!(client.try_get_certificate(&cert_id3).is_ok());
 }
 
 #[test]
@@ -98,11 +118,9 @@ fn test_get_nonexistent_certificate() {
     match result {
         // Contract call succeeded but returned our custom error
         Err(Ok(ProvenanceError::CertificateNotFound)) => {}
-        other => panic!(
-            "expected CertificateNotFound error, got: {other:?}"
-        ),
+        other => panic!("expected CertificateNotFound error, got: {other:?}"),
     }
-    assert_eq!(result, Ok(Err(ProvenanceError::CertificateNotFound)));
+    assert_eq!(result, Err(Ok(ProvenanceError::CertificateNotFound)));
 }
 
 #[test]
@@ -114,14 +132,74 @@ fn test_mint_without_initialization() {
     let contract_id = env.register(ProvenanceContract, ());
     let client = ProvenanceContractClient::new(&env, &contract_id);
 
+    let oracle = Address::generate(&env);
     let owner = Address::generate(&env);
     let details = CertificateDetails {
         storage_id: String::from_str(&env, "s"),
         manifest_hash: String::from_str(&env, "hash"),
         attestation_hash: String::from_str(&env, "a"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
     };
 
-    client.mint(&owner, &details);
+    client.mint(&oracle, &owner, &details);
+}
+
+#[test]
+fn test_minters_are_authorized_by_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ProvenanceContract, ());
+    let client = ProvenanceContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let registry = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.add_minter(&registry);
+    assert_eq!(env.auths()[0].0, admin);
+    client.add_minter(&oracle);
+    assert!(client.is_minter(&registry));
+
+    let details = CertificateDetails {
+        storage_id: String::from_str(&env, "sid"),
+        manifest_hash: String::from_str(&env, "mhash"),
+        attestation_hash: String::from_str(&env, "ahash"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
+    };
+    let mut other = details.clone();
+    other.manifest_hash = String::from_str(&env, "other");
+
+    // Every authorized minter can issue certificates.
+    let registry_cert = client.mint(&registry, &Address::generate(&env), &details);
+    assert_eq!(env.auths()[0].0, registry);
+    let oracle_cert = client.mint(&oracle, &Address::generate(&env), &other);
+    assert_ne!(registry_cert, oracle_cert);
+
+    client.remove_minter(&oracle);
+    assert!(!client.is_minter(&oracle));
+    let reason = String::from_str(&env, "KeyCompromised");
+    assert_eq!(
+        client.try_flag_certificate(&oracle, &oracle_cert, &reason),
+        Err(Ok(ProvenanceError::UnauthorizedMinter))
+    );
+    other.manifest_hash = String::from_str(&env, "third");
+    assert_eq!(
+        client.try_mint(&oracle, &Address::generate(&env), &other),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            ProvenanceError::UnauthorizedMinter as u32
+        )))
+    );
+    assert_eq!(
+        client.get_certificate(&oracle_cert).manifest_hash,
+        String::from_str(&env, "other")
+    );
 }
 
 #[test]
@@ -151,14 +229,19 @@ fn test_certificate_minted_event() {
     let oracle = Address::generate(&env);
     let owner = Address::generate(&env);
 
-    client.initialize(&oracle);
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
     let details = CertificateDetails {
         storage_id: String::from_str(&env, "sid"),
         manifest_hash: String::from_str(&env, "mhash"),
         attestation_hash: String::from_str(&env, "ahash"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
     };
 
-    let cert_id = client.mint(&owner, &details);
+    let cert_id = client.mint(&oracle, &owner, &details);
     assert_eq!(cert_id, 1);
 
     let events = Events::all(&env.events());
@@ -179,14 +262,19 @@ fn test_immutable_timestamp() {
     let oracle = Address::generate(&env);
     let owner = Address::generate(&env);
 
-    client.initialize(&oracle);
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
     let details = CertificateDetails {
         storage_id: String::from_str(&env, "sid"),
         manifest_hash: String::from_str(&env, "mhash"),
         attestation_hash: String::from_str(&env, "ahash"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
     };
 
-    let cert_id = client.mint(&owner, &details);
+    let cert_id = client.mint(&oracle, &owner, &details);
     let cert = client.get_certificate(&cert_id);
     let stored_timestamp = cert.timestamp;
     assert_eq!(
@@ -197,8 +285,7 @@ fn test_immutable_timestamp() {
 
     let cert_again = client.get_certificate(&cert_id);
     assert_eq!(
-        cert_again.timestamp,
-        stored_timestamp,
+        cert_again.timestamp, stored_timestamp,
         "timestamp is immutable and must not change on subsequent reads"
     );
 }
@@ -215,22 +302,235 @@ fn test_prevent_duplicate_manifest_hash() {
     let oracle = Address::generate(&env);
     let owner = Address::generate(&env);
 
-    client.initialize(&oracle);
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
 
     let details1 = CertificateDetails {
         storage_id: String::from_str(&env, "s1"),
         manifest_hash: String::from_str(&env, "dup_hash"),
         attestation_hash: String::from_str(&env, "a1"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
     };
     let details2 = CertificateDetails {
         storage_id: String::from_str(&env, "s2"),
         manifest_hash: String::from_str(&env, "dup_hash"),
         attestation_hash: String::from_str(&env, "a2"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
     };
 
     // First mint should succeed and create the mapping
-    let _ = client.mint(&owner, &details1);
+    let _ = client.mint(&oracle, &owner, &details1);
 
     // Second mint with the same manifest_hash must fail with a clear error
-    let _ = client.mint(&owner, &details2);
+    let _ = client.mint(&oracle, &owner, &details2);
+}
+
+#[test]
+fn test_flag_certificate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ProvenanceContract, ());
+    let client = ProvenanceContractClient::new(&env, &contract_id);
+    let oracle = Address::generate(&env);
+    let owner = Address::generate(&env);
+
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
+    let details = CertificateDetails {
+        storage_id: String::from_str(&env, "sid"),
+        manifest_hash: String::from_str(&env, "mhash"),
+        attestation_hash: String::from_str(&env, "ahash"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
+    };
+    let cert_id = client.mint(&oracle, &owner, &details);
+    assert_eq!(client.get_flag(&cert_id), None);
+
+    let reason = String::from_str(&env, "KeyCompromised");
+    client.flag_certificate(&oracle, &cert_id, &reason);
+    assert_eq!(env.auths()[0].0, oracle);
+
+    let events_str = std::format!("{:#?}", Events::all(&env.events()));
+    assert!(events_str.contains("certificate_flagged"));
+
+    let flag = client.get_flag(&cert_id).unwrap();
+    assert_eq!(flag.reason, reason);
+    assert_eq!(flag.flagged_at, env.ledger().timestamp());
+}
+
+#[test]
+fn test_flag_nonexistent_certificate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ProvenanceContract, ());
+    let client = ProvenanceContractClient::new(&env, &contract_id);
+    let oracle = Address::generate(&env);
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
+
+    let result =
+        client.try_flag_certificate(&oracle, &42, &String::from_str(&env, "KeyCompromised"));
+    assert_eq!(result, Err(Ok(ProvenanceError::CertificateNotFound)));
+}
+
+#[test]
+fn test_revoke_certificate_releases_manifest_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ProvenanceContract, ());
+    let client = ProvenanceContractClient::new(&env, &contract_id);
+    let oracle = Address::generate(&env);
+    let squatter = Address::generate(&env);
+    let creator = Address::generate(&env);
+
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
+    let details = CertificateDetails {
+        storage_id: String::from_str(&env, "sid"),
+        manifest_hash: String::from_str(&env, "mhash"),
+        attestation_hash: String::from_str(&env, "ahash"),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
+    };
+    let revoked_id = client.mint(&oracle, &squatter, &details);
+
+    let reason = String::from_str(&env, "ClaimContested");
+    client.revoke_certificate(&oracle, &revoked_id, &reason);
+    assert_eq!(env.auths()[0].0, oracle);
+
+    let events_str = std::format!("{:#?}", Events::all(&env.events()));
+    assert!(events_str.contains("certificate_revoked"));
+    assert_eq!(client.get_flag(&revoked_id).unwrap().reason, reason);
+
+    // The manifest hash can now be certified to its rightful creator.
+    let cert_id = client.mint(&oracle, &creator, &details);
+    assert_ne!(cert_id, revoked_id);
+    assert_eq!(client.get_certificate(&cert_id).creator, creator);
+    assert_eq!(client.get_certificate(&revoked_id).creator, squatter);
+
+    assert_eq!(
+        client.try_revoke_certificate(&oracle, &42, &reason),
+        Err(Ok(ProvenanceError::CertificateNotFound))
+    );
+}
+
+#[test]
+fn test_certificate_records_hash_algorithm() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ProvenanceContract, ());
+    let client = ProvenanceContractClient::new(&env, &contract_id);
+    let oracle = Address::generate(&env);
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
+
+    let details = CertificateDetails {
+        storage_id: String::from_str(&env, "sid"),
+        manifest_hash: String::from_str(&env, "mhash"),
+        attestation_hash: String::from_str(&env, "ahash"),
+        hash_algorithm: HashAlgorithm::Keccak256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
+    };
+    let cert_id = client.mint(&oracle, &Address::generate(&env), &details);
+
+    let cert = client.get_certificate(&cert_id);
+    assert_eq!(cert.hash_algorithm, HashAlgorithm::Keccak256);
+    assert_eq!(HashAlgorithm::Keccak256 as u32, 0x1b);
+}
+
+#[test]
+fn test_certificate_records_chunked_layout() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ProvenanceContract, ());
+    let client = ProvenanceContractClient::new(&env, &contract_id);
+    let oracle = Address::generate(&env);
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
+
+    let commitment = MerkleCommitment {
+        root: soroban_sdk::BytesN::from_array(&env, &[7; 32]),
+        chunk_size: 1024,
+        chunk_count: 12,
+    };
+    let details = CertificateDetails {
+        storage_id: String::from_str(&env, "sid"),
+        manifest_hash: String::from_str(&env, "0707"),
+        attestation_hash: String::from_str(&env, ""),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Chunked(commitment.clone()),
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
+    };
+    let cert_id = client.mint(&oracle, &Address::generate(&env), &details);
+
+    assert_eq!(
+        client.get_certificate(&cert_id).layout,
+        ContentLayout::Chunked(commitment)
+    );
+}
+
+#[test]
+fn test_certificate_records_job_result() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(ProvenanceContract, ());
+    let client = ProvenanceContractClient::new(&env, &contract_id);
+    let oracle = Address::generate(&env);
+    client.initialize(&Address::generate(&env));
+    client.add_minter(&oracle);
+
+    let integrity = CertificateDetails {
+        storage_id: String::from_str(&env, "sid"),
+        manifest_hash: String::from_str(&env, "abcd"),
+        attestation_hash: String::from_str(&env, ""),
+        hash_algorithm: HashAlgorithm::Sha256,
+        layout: ContentLayout::Whole,
+        job: JobResult::ContentIntegrity,
+        job_digest: BytesN::from_array(&env, &[0; 32]),
+    };
+    let detection = JobResult::AiGenerationDetection(AiDetectionResult {
+        score_bps: 120,
+        model: String::from_str(&env, "detector-v2"),
+    });
+    let mut ai = integrity.clone();
+    ai.job = detection.clone();
+
+    // The same content can be certified once per kind of verification.
+    let integrity_id = client.mint(&oracle, &Address::generate(&env), &integrity);
+    let ai_id = client.mint(&oracle, &Address::generate(&env), &ai);
+    assert_ne!(integrity_id, ai_id);
+    assert_eq!(
+        client.get_certificate(&integrity_id).job,
+        JobResult::ContentIntegrity
+    );
+    assert_eq!(client.get_certificate(&ai_id).job, detection);
+    assert_eq!(detection.job_type(), JobType::AiGenerationDetection);
+
+    // ...and once per set of job parameters.
+    let mut stricter = ai.clone();
+    stricter.job_digest = BytesN::from_array(&env, &[1; 32]);
+    let stricter_id = client.mint(&oracle, &Address::generate(&env), &stricter);
+    assert_ne!(stricter_id, ai_id);
+    assert_eq!(
+        client.get_certificate(&stricter_id).job_digest,
+        stricter.job_digest
+    );
 }
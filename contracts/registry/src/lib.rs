@@ -1,11 +1,45 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, xdr::ToXdr, Address, BytesN, Env, String,
+    contract, contracterror, contractevent, contractimpl, contracttype, xdr::ToXdr, Address,
+    Bytes, BytesN, Env, String, Vec,
 };
 
 mod provenance {
-    use soroban_sdk::{contractclient, contracttype, Address, Env, String};
+    use soroban_sdk::{contractclient, contracttype, Address, BytesN, Env, String};
+
+    pub use super::{ContentLayout, HashAlgorithm};
+
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct ManifestSignatureResult {
+        pub signer: String,
+    }
+
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct C2paResult {
+        pub claim_generator: String,
+        pub active_manifest: BytesN<32>,
+    }
+
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct AiDetectionResult {
+        pub score_bps: u32,
+        pub model: String,
+    }
+
+    /// The registry only certifies content integrity.
+    #[allow(dead_code)]
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum JobResult {
+        ContentIntegrity,
+        ManifestSignature(ManifestSignatureResult),
+        C2paValidation(C2paResult),
+        AiGenerationDetection(AiDetectionResult),
+    }
 
     #[contracttype]
     #[derive(Clone, Debug, Eq, PartialEq)]
@@ -13,11 +47,20 @@ mod provenance {
         pub storage_id: String,
         pub manifest_hash: String,
         pub attestation_hash: String,
+        pub hash_algorithm: HashAlgorithm,
+        pub layout: ContentLayout,
+        pub job: JobResult,
+        /// Integrity checks take no parameters and are identified by an
+        /// all-zero digest.
+        pub job_digest: BytesN<32>,
     }
 
+    #[allow(dead_code)]
     #[contractclient(name = "Client")]
     pub trait ProvenanceClient {
-        fn mint(env: &Env, to: Address, details: CertificateDetails) -> u64;
+        fn mint(env: &Env, minter: Address, to: Address, details: CertificateDetails) -> u64;
+        fn flag_certificate(env: &Env, minter: Address, certificate_id: u64, reason: String);
+        fn revoke_certificate(env: &Env, minter: Address, certificate_id: u64, reason: String);
     }
 }
 
@@ -32,6 +75,120 @@ pub enum VerificationError {
     AlreadyProcessed = 5,
     InvalidTeeHash = 6,
     DuplicateHash = 7,
+    ProviderExists = 8,
+    ProviderNotFound = 9,
+    InvalidProviderState = 10,
+    InvalidMetadata = 11,
+    KeyNotActive = 12,
+    KeyInUse = 13,
+    InvalidSuspension = 14,
+    InvalidCompromise = 15,
+    NotSuspect = 16,
+    ProvenanceCallFailed = 17,
+    NoClaim = 18,
+    NotContestable = 19,
+    InvalidHash = 20,
+    InvalidChunkParams = 21,
+    ChallengePending = 22,
+    EmptyContent = 23,
+    ContentTooLarge = 24,
+    RequestExists = 25,
+    InvalidTtl = 26,
+    KeyCompromised = 27,
+}
+
+/// Algorithm that produced a content or manifest digest.
+///
+/// The discriminants are the multihash codes of the algorithms, so a digest
+/// tagged with its algorithm is a multihash.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum HashAlgorithm {
+    Sha256 = 0x12,
+    Keccak256 = 0x1b,
+    Blake3 = 0x1e,
+    Blake2b256 = 0xb220,
+}
+
+/// Commitment to content split into fixed-size chunks.
+///
+/// `root` is the root of a binary SHA-256 Merkle tree whose leaves are
+/// `sha256(0x00 || chunk)` and whose inner nodes are
+/// `sha256(0x01 || left || right)`; a node without a sibling is promoted to
+/// the next level unchanged. Every chunk is `chunk_size` bytes except the
+/// last, which may be shorter.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleCommitment {
+    pub root: BytesN<32>,
+    pub chunk_size: u32,
+    pub chunk_count: u32,
+}
+
+/// How content passed to the registry was committed to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ContentLayout {
+    /// The content hash is a digest of the whole content.
+    Whole,
+    /// The content hash is the hex-encoded Merkle root of the chunk commitment.
+    Chunked(MerkleCommitment),
+}
+
+/// A sampled chunk together with its Merkle inclusion proof, listing sibling
+/// hashes from the leaf level upwards.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChunkProof {
+    pub index: u32,
+    pub chunk: Bytes,
+    pub siblings: Vec<BytesN<32>>,
+}
+
+/// Chunk indices the registry expects proofs for, see
+/// `begin_chunked_verification`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChunkChallenge {
+    pub commitment: MerkleCommitment,
+    pub owner: Address,
+    pub minter: Address,
+    pub indices: Vec<u32>,
+}
+
+/// Why a verification was rejected.
+///
+/// The discriminants are stable codes that clients can match on; new reasons
+/// are only ever appended.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RejectionReason {
+    /// The attesting provider is not trusted by the registry.
+    UnauthorizedProvider = 1,
+    /// The TEE measurement is not trusted by the registry.
+    UntrustedTee = 2,
+    /// The attestation does not refer to the request being processed.
+    AttestationMismatch = 3,
+    /// The computed content hash differs from the expected hash.
+    HashMismatch = 4,
+    /// The request outlived its deadline.
+    Expired = 5,
+    /// The attesting provider is suspended.
+    ProviderSuspended = 6,
+    /// The provider is not allowed to present this TEE measurement.
+    ProviderTeeNotAllowed = 7,
+    /// The signing key was rotated out and its overlap window elapsed.
+    KeyRetired = 8,
+    /// The signing key was declared compromised.
+    KeyCompromised = 9,
+    /// The content hash algorithm cannot be recomputed on-chain.
+    UnsupportedAlgorithm = 10,
+    /// Another account holds a revealed priority claim on the content hash.
+    ClaimConflict = 11,
+    /// A sampled chunk did not prove inclusion under the committed Merkle root.
+    ChunkProofInvalid = 12,
 }
 
 #[contracttype]
@@ -39,8 +196,12 @@ pub enum VerificationError {
 pub enum RequestState {
     Pending,
     Verified,
-    Rejected(String),
+    Rejected(RejectionReason),
     Failed,
+    /// The deadline passed before the request was processed.
+    Expired,
+    /// The requester withdrew the request before it was processed.
+    Cancelled,
 }
 
 #[contracttype]
@@ -48,6 +209,7 @@ pub enum RequestState {
 pub struct VerificationResult {
     pub success: bool,
     pub content_hash: String,
+    pub hash_algorithm: HashAlgorithm,
     pub certificate_id: Option<u64>,
     pub state: RequestState,
 }
@@ -56,7 +218,24 @@ pub struct VerificationResult {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct VerificationRequest {
     pub id: u64,
+    /// Account that submitted the request.
+    pub requester: Address,
+    /// Digest of the content to verify.
+    pub content_hash: BytesN<32>,
+    pub hash_algorithm: HashAlgorithm,
+    /// Off-chain storage location of the content.
+    pub storage_id: String,
+    /// Ledger sequence at which the request was submitted.
+    pub created_at: u32,
+    /// Last ledger sequence at which the request can be processed.
+    pub deadline: u32,
     pub state: RequestState,
+    /// Signing key whose attestation verified the request.
+    pub verified_by: Option<BytesN<32>>,
+    /// Ledger timestamp at which the request was verified.
+    pub verified_at: Option<u64>,
+    /// Provenance certificate minted for the verified request.
+    pub certificate_id: Option<u64>,
 }
 
 #[contracttype]
@@ -74,6 +253,85 @@ pub enum DataKey {
     Provider(BytesN<32>),
     TeeHash(BytesN<32>),
     Provenance,
+    /// Application and metadata record for a provider key.
+    ProviderRecord(BytesN<32>),
+    /// Allow-matrix entry binding a provider to a TEE measurement it may present.
+    ProviderTee(BytesN<32>, BytesN<32>),
+    /// Maps a rotated-in or rotated-out signing key to its provider.
+    ProviderKey(BytesN<32>),
+    /// Signing key a provider currently rotates from.
+    ActiveKey(BytesN<32>),
+    /// Ledgers an old signing key stays valid after rotation.
+    KeyOverlap,
+    /// Temporary suspension of a provider.
+    Suspension(BytesN<32>),
+    /// Declared compromise window of a signing key.
+    Compromise(BytesN<32>),
+    /// Whether `verify_and_mint` accepts legacy truncated hashes.
+    LegacyHashMode,
+    /// Delegate allowed to authorize mints for an owner.
+    Delegate(Address, Address),
+    /// Hidden priority claim, keyed by its commitment.
+    ClaimCommitment(BytesN<32>),
+    /// Revealed priority claim on a canonical hex manifest hash.
+    Claim(String),
+    /// Certificate minted by `verify_and_mint` for a manifest hash.
+    Minted(String),
+    /// Outstanding chunk-sampling challenge for a Merkle root, per minter.
+    ChunkChallenge(BytesN<32>, Address),
+    /// Id assigned to the next submitted verification request.
+    NextRequestId,
+    /// Ledgers a new request stays open before it expires.
+    RequestTtl,
+    /// Approved provider keys managed by an operator account.
+    OperatorKeys(Address),
+}
+
+/// Largest raw content accepted by `verify_and_mint_bytes`. Bigger files go
+/// through chunked verification instead.
+const MAX_CONTENT_BYTES: u32 = 64 * 1024;
+
+/// Number of chunks sampled when verifying chunked content.
+const CHUNK_SAMPLES: u32 = 4;
+
+/// Largest chunk accepted for chunked verification, so that all sampled
+/// chunks and their proofs fit in one transaction.
+const MAX_CHUNK_SIZE: u32 = 16 * 1024;
+
+/// Ledgers (~1 hour at 5s per ledger) a chunk-sampling challenge stays open.
+const CHUNK_CHALLENGE_TTL_LEDGERS: u32 = 720;
+
+/// Default number of ledgers (~1 day at 5s per ledger) a verification request
+/// stays open.
+const DEFAULT_REQUEST_TTL_LEDGERS: u32 = 17_280;
+
+/// Longest request TTL (~30 days at 5s per ledger) the admin may configure.
+const MAX_REQUEST_TTL_LEDGERS: u32 = 518_400;
+
+/// Default number of ledgers (~1 day at 5s per ledger) during which both the
+/// old and the new signing key are accepted after a rotation.
+const DEFAULT_KEY_OVERLAP_LEDGERS: u32 = 17_280;
+
+/// Hex-encoded multihash prefixes (code and 32-byte length) of the supported
+/// digest algorithms.
+const MULTIHASH_PREFIXES: [(&[u8], HashAlgorithm); 4] = [
+    (b"1220", HashAlgorithm::Sha256),
+    (b"1b20", HashAlgorithm::Keccak256),
+    (b"1e20", HashAlgorithm::Blake3),
+    (b"a0e40220", HashAlgorithm::Blake2b256),
+];
+
+/// Number of digest bytes kept by the legacy truncated hash format.
+const LEGACY_HASH_BYTES: usize = 8;
+
+/// Longest accepted `expected_hash` string (a hex multihash).
+const MAX_EXPECTED_HASH_LEN: usize = 72;
+
+/// Expected hash supplied to `verify_and_mint`, after parsing.
+enum ExpectedHash {
+    Full(HashAlgorithm, [u8; 32]),
+    /// Truncated SHA-256, accepted only in legacy hash mode.
+    Legacy([u8; LEGACY_HASH_BYTES]),
 }
 
 #[contracttype]
@@ -88,6 +346,214 @@ pub struct TeeHashEventData {
     pub hash: BytesN<32>,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderTeeEventData {
+    pub provider: BytesN<32>,
+    pub hash: BytesN<32>,
+}
+
+/// Application statement signed by the key a provider applies with, proving
+/// the applicant holds it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderApplication {
+    /// The registry applied to, so the statement cannot be replayed elsewhere.
+    pub registry: Address,
+    pub key: BytesN<32>,
+    pub operator: Address,
+}
+
+/// Rotation statement signed by a provider's current signing key.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyRotation {
+    /// The registry the key is rotated in, so the statement cannot be
+    /// replayed elsewhere.
+    pub registry: Address,
+    pub provider: BytesN<32>,
+    pub current_key: BytesN<32>,
+    pub new_key: BytesN<32>,
+}
+
+/// Signing key entry for a provider whose keys have been rotated.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderKey {
+    pub provider: BytesN<32>,
+    /// Ledger sequence at which the key stops being accepted; `None` while current.
+    pub retires_at: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyRotatedEventData {
+    pub provider: BytesN<32>,
+    pub old_key: BytesN<32>,
+    pub new_key: BytesN<32>,
+    pub retires_at: u32,
+}
+
+/// Temporary suspension placed on a provider by the admin.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Suspension {
+    /// Ledger sequence at which the suspension lapses on its own.
+    pub until_ledger: u32,
+    pub reason: String,
+    /// Ledger timestamp at which the suspension was placed.
+    pub suspended_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SuspensionEventData {
+    pub provider: BytesN<32>,
+    pub until_ledger: u32,
+    pub reason: String,
+}
+
+/// Declared compromise of a provider signing key.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Compromise {
+    pub provider: BytesN<32>,
+    /// Ledger timestamp from which attestations by the key are untrusted.
+    pub since: u64,
+    /// Ledger timestamp at which the compromise was declared.
+    pub declared_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompromiseEventData {
+    pub provider: BytesN<32>,
+    pub key: BytesN<32>,
+    pub since: u64,
+}
+
+/// Preimage of a claim commitment, see `register_claim`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimPreimage {
+    pub owner: Address,
+    /// Canonical lower-case hex manifest hash of the claimed content.
+    pub manifest_hash: String,
+}
+
+/// Revealed priority claim on a content hash, see `contest_mint`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimRecord {
+    pub owner: Address,
+    /// Ledger timestamp at which the claim commitment was registered.
+    pub claimed_at: u64,
+}
+
+/// Certificate minted by `verify_and_mint` for a manifest hash.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintRecord {
+    pub owner: Address,
+    pub certificate_id: u64,
+    /// Ledger timestamp of the mint.
+    pub minted_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimEventData {
+    pub manifest_hash: String,
+    pub claimant: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestSubmitted {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+    pub hash_algorithm: HashAlgorithm,
+    pub requester: Address,
+    pub deadline: u32,
+}
+
+/// Emitted when an attestation verifies a request. `certificate_id` is
+/// `None` when the mint failed and the request ended up `Failed`.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestVerified {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+    pub verified_by: BytesN<32>,
+    pub certificate_id: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestRejected {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+    pub reason: RejectionReason,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestCancelled {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestExpired {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub content_hash: BytesN<32>,
+}
+
+/// Lifecycle of a provider application.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProviderStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Self-declared provider details, supplied on `apply` and `update_provider`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderMetadata {
+    pub name: String,
+    pub contact_uri: String,
+    /// Account that manages the provider and authorizes metadata changes.
+    pub operator: Address,
+    /// Enclave technologies the provider runs (e.g. "sgx", "sev-snp", "nitro").
+    pub enclave_types: Vec<String>,
+    pub signing_keys: Vec<BytesN<32>>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderRecord {
+    pub key: BytesN<32>,
+    pub metadata: ProviderMetadata,
+    pub status: ProviderStatus,
+    /// Ledger timestamp of the application.
+    pub applied_at: u64,
+    /// Ledger timestamp of the last status or metadata change.
+    pub updated_at: u64,
+}
+
 #[contract]
 pub struct Registry;
 
@@ -112,249 +578,1565 @@ impl Registry {
     /// 
     /// # Arguments
     /// * `content` - Content to verify
-    /// * `expected_hash` - Expected hash of the content (as hex string)
+    /// * `expected_hash` - Expected digest of the content, either as 64 hex
+    ///   characters (optionally `0x`-prefixed), taken to be SHA-256, or as a
+    ///   hex multihash whose prefix selects the algorithm (`1220` SHA-256,
+    ///   `1b20` Keccak-256). The legacy 16-character truncated SHA-256 form is
+    ///   only accepted while legacy hash mode is enabled.
     /// * `owner` - Owner address for the certificate
+    /// * `minter` - Account authorizing the mint: the owner or one of the
+    ///   owner's delegates (see `add_delegate`)
     /// 
     /// # Returns
-    /// VerificationResult with success status and certificate ID if minted
+    /// VerificationResult with success status and certificate ID if minted.
+    /// A matching hash is rejected with `ClaimConflict` when another account
+    /// holds a revealed priority claim on it (see `contest_mint`). Returns
+    /// `InvalidHash` if `expected_hash` is not one of the accepted forms.
     pub fn verify_and_mint(
         env: Env,
         content: String,
         expected_hash: String,
         owner: Address,
-    ) -> VerificationResult {
-        // Perform content verification
-        let computed_hash_string = Self::compute_hash(&env, &content);
-        let verification_success = computed_hash_string == expected_hash;
-        
-        if !verification_success {
-            // Verification failed - return without minting
-            return VerificationResult {
-                success: false,
-                content_hash: computed_hash_string,
-                certificate_id: None,
-                state: RequestState::Rejected(String::from_str(&env, "HashMismatch")),
-            };
+        minter: Address,
+    ) -> Result<VerificationResult, VerificationError> {
+        Self::authorize_minter(&env, &owner, &minter)?;
+        Self::verify_expected(&env, &content.to_bytes(), &expected_hash, &owner)
+    }
+
+    /// Same as `verify_and_mint`, for binary content such as images or PDFs.
+    ///
+    /// `content` holds the raw file bytes, so the expected hash is the plain
+    /// digest of the original file. Content must be non-empty and at most
+    /// 64 KiB; larger files should use `begin_chunked_verification`.
+    pub fn verify_and_mint_bytes(
+        env: Env,
+        content: Bytes,
+        expected_hash: String,
+        owner: Address,
+        minter: Address,
+    ) -> Result<VerificationResult, VerificationError> {
+        if content.is_empty() {
+            return Err(VerificationError::EmptyContent);
         }
-        
-        // Verification succeeded - mint provenance certificate
-        let certificate_id = Self::mint_certificate(&env, &computed_hash_string, &owner);
-        
-        match certificate_id {
-            Ok(cert_id) => VerificationResult {
-                success: true,
-                content_hash: computed_hash_string,
-                certificate_id: Some(cert_id),
-                state: RequestState::Verified,
+        if content.len() > MAX_CONTENT_BYTES {
+            return Err(VerificationError::ContentTooLarge);
+        }
+        Self::authorize_minter(&env, &owner, &minter)?;
+        Self::verify_expected(&env, &content, &expected_hash, &owner)
+    }
+
+    /// Same as `verify_and_mint`, with the expected digest given as raw bytes
+    /// together with the algorithm that produced it.
+    pub fn verify_and_mint_digest(
+        env: Env,
+        content: String,
+        expected_hash: BytesN<32>,
+        algorithm: HashAlgorithm,
+        owner: Address,
+        minter: Address,
+    ) -> Result<VerificationResult, VerificationError> {
+        Self::authorize_minter(&env, &owner, &minter)?;
+        Ok(Self::verify_digest(
+            &env,
+            &content.to_bytes(),
+            algorithm,
+            expected_hash.to_array(),
+            &owner,
+        ))
+    }
+
+    /// Allow `delegate` to authorize mints on behalf of `owner`.
+    /// The owner must authorize the call.
+    pub fn add_delegate(env: Env, owner: Address, delegate: Address) {
+        owner.require_auth();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Delegate(owner, delegate), &true);
+    }
+
+    /// Revoke a delegate previously added with `add_delegate`.
+    /// The owner must authorize the call.
+    pub fn remove_delegate(env: Env, owner: Address, delegate: Address) {
+        owner.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Delegate(owner, delegate));
+    }
+
+    /// Return whether `delegate` may authorize mints on behalf of `owner`.
+    pub fn is_delegate(env: Env, owner: Address, delegate: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Delegate(owner, delegate))
+            .unwrap_or(false)
+    }
+
+    /// Record a hidden priority claim on content that has not been published yet.
+    ///
+    /// `commitment` is the SHA-256 of the XDR encoding of a `ClaimPreimage`
+    /// naming `owner` and the canonical hex manifest hash of the content.
+    /// Because it reveals neither, it cannot be front-run; the claimant later
+    /// reveals it through `contest_mint` to prove it predates a squatted mint.
+    pub fn register_claim(env: Env, owner: Address, commitment: BytesN<32>) {
+        owner.require_auth();
+
+        let key = DataKey::ClaimCommitment(commitment);
+        if !env.storage().persistent().has(&key) {
+            env.storage()
+                .persistent()
+                .set(&key, &env.ledger().timestamp());
+        }
+    }
+
+    /// Return the ledger timestamp at which a claim commitment was registered.
+    pub fn get_claim_commitment(env: Env, commitment: BytesN<32>) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ClaimCommitment(commitment))
+    }
+
+    /// Return the revealed priority claim on a canonical hex manifest hash, if any.
+    pub fn get_claim(env: Env, manifest_hash: String) -> Option<ClaimRecord> {
+        env.storage().persistent().get(&DataKey::Claim(manifest_hash))
+    }
+
+    /// Return the mint recorded for a canonical hex manifest hash, if any.
+    pub fn get_mint(env: Env, manifest_hash: String) -> Option<MintRecord> {
+        env.storage().persistent().get(&DataKey::Minted(manifest_hash))
+    }
+
+    /// Contest a certificate minted to another account for content the
+    /// `claimant` committed to (see `register_claim`) before the mint.
+    ///
+    /// `content_hash` accepts the same forms as `verify_and_mint`, except the
+    /// legacy truncated one. On success the claim is revealed, so further
+    /// mints of the hash to anyone but the claimant are rejected with
+    /// `ClaimConflict`, the contested certificate is revoked on the provenance
+    /// contract with reason `ClaimContested` so the claimant can mint the
+    /// hash, and the revoked certificate's id is returned.
+    /// Returns `NoClaim` without a matching commitment and `NotContestable`
+    /// if the mint went to the claimant or predates the commitment.
+    pub fn contest_mint(
+        env: Env,
+        claimant: Address,
+        content_hash: String,
+    ) -> Result<u64, VerificationError> {
+        claimant.require_auth();
+
+        let manifest_hash = Self::canonical_manifest_hash(&env, &content_hash)?;
+        let preimage = ClaimPreimage {
+            owner: claimant.clone(),
+            manifest_hash: manifest_hash.clone(),
+        };
+        let commitment: BytesN<32> = env.crypto().sha256(&preimage.to_xdr(&env)).into();
+        let claimed_at = Self::get_claim_commitment(env.clone(), commitment)
+            .ok_or(VerificationError::NoClaim)?;
+        let mint = Self::get_mint(env.clone(), manifest_hash.clone())
+            .ok_or(VerificationError::NotFound)?;
+        if mint.owner == claimant || claimed_at >= mint.minted_at {
+            return Err(VerificationError::NotContestable);
+        }
+
+        let provenance_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Provenance)
+            .ok_or(VerificationError::ProvenanceCallFailed)?;
+        let provenance_client = provenance::Client::new(&env, &provenance_addr);
+        if !matches!(
+            provenance_client.try_revoke_certificate(
+                &env.current_contract_address(),
+                &mint.certificate_id,
+                &String::from_str(&env, "ClaimContested"),
+            ),
+            Ok(Ok(()))
+        ) {
+            return Err(VerificationError::ProvenanceCallFailed);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::Claim(manifest_hash.clone()),
+            &ClaimRecord {
+                owner: claimant.clone(),
+                claimed_at,
             },
-            Err(_) => {
-                // Minting failed but verification succeeded
-                // Handle error gracefully and return success without certificate
-                VerificationResult {
-                    success: true,
-                    content_hash: computed_hash_string,
-                    certificate_id: None,
-                    state: RequestState::Failed,
-                }
+        );
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "MintContested"),
+                manifest_hash.clone(),
+            ),
+            ClaimEventData {
+                manifest_hash,
+                claimant,
+            },
+        );
+
+        Ok(mint.certificate_id)
+    }
+
+    /// Require `minter` to authorize and to be the owner or one of its delegates.
+    fn authorize_minter(
+        env: &Env,
+        owner: &Address,
+        minter: &Address,
+    ) -> Result<(), VerificationError> {
+        minter.require_auth();
+        if minter != owner && !Self::is_delegate(env.clone(), owner.clone(), minter.clone()) {
+            return Err(VerificationError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Canonical lower-case hex form of a full (non-legacy) expected hash.
+    fn canonical_manifest_hash(
+        env: &Env,
+        content_hash: &String,
+    ) -> Result<String, VerificationError> {
+        match Self::parse_expected_hash(content_hash) {
+            Some(ExpectedHash::Full(_, digest)) => Ok(Self::hex_string(env, &digest)),
+            _ => Err(VerificationError::InvalidHash),
+        }
+    }
+
+    /// Start verifying content committed as a Merkle root over fixed-size
+    /// chunks, for media too large to pass to `verify_and_mint` in one call.
+    ///
+    /// Picks `CHUNK_SAMPLES` distinct chunk indices (every chunk for small
+    /// content) with the ledger PRNG and returns them; the caller then submits
+    /// those chunks with inclusion proofs to `complete_chunked_verification`
+    /// before the challenge expires. `minter` authorizes as for
+    /// `verify_and_mint`, and each minter has its own challenge per root.
+    pub fn begin_chunked_verification(
+        env: Env,
+        commitment: MerkleCommitment,
+        owner: Address,
+        minter: Address,
+    ) -> Result<Vec<u32>, VerificationError> {
+        Self::authorize_minter(&env, &owner, &minter)?;
+
+        if commitment.chunk_size == 0
+            || commitment.chunk_size > MAX_CHUNK_SIZE
+            || commitment.chunk_count == 0
+        {
+            return Err(VerificationError::InvalidChunkParams);
+        }
+        let key = DataKey::ChunkChallenge(commitment.root.clone(), minter.clone());
+        if env.storage().temporary().has(&key) {
+            return Err(VerificationError::ChallengePending);
+        }
+
+        // Floyd's algorithm: sample without replacement in a fixed number of draws.
+        let count = commitment.chunk_count;
+        let mut indices: Vec<u32> = Vec::new(&env);
+        for upper in count - CHUNK_SAMPLES.min(count)..count {
+            let index: u64 = env.prng().gen_range(0..=u64::from(upper));
+            let index = index as u32;
+            if indices.contains(index) {
+                indices.push_back(upper);
+            } else {
+                indices.push_back(index);
             }
         }
+
+        let challenge = ChunkChallenge {
+            commitment,
+            owner,
+            minter,
+            indices: indices.clone(),
+        };
+        env.storage().temporary().set(&key, &challenge);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, 0, CHUNK_CHALLENGE_TTL_LEDGERS);
+
+        Ok(indices)
     }
-    
-    /// Compute hash of content using SHA-256 and return as hex string
-    fn compute_hash(env: &Env, content: &String) -> String {
-        // Convert string to bytes and compute SHA-256 hash
-        let content_bytes = content.to_bytes();
-        let hash: BytesN<32> = env.crypto().sha256(&content_bytes).into();
-        
-        // Convert first 8 bytes to hex string
-        let hash_array = hash.to_array();
-        let hex_chars: [u8; 16] = [
-            b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7',
-            b'8', b'9', b'a', b'b', b'c', b'd', b'e', b'f',
+
+    /// Finish the chunked verification `minter` started with
+    /// `begin_chunked_verification`.
+    ///
+    /// `proofs` must answer the challenged indices in order. If every proof
+    /// checks out against the committed root, a certificate is minted whose
+    /// manifest hash is the hex-encoded root and which records the chunk
+    /// parameters; otherwise the result is rejected with `ChunkProofInvalid`.
+    /// The challenge is consumed either way.
+    pub fn complete_chunked_verification(
+        env: Env,
+        root: BytesN<32>,
+        minter: Address,
+        proofs: Vec<ChunkProof>,
+    ) -> Result<VerificationResult, VerificationError> {
+        let key = DataKey::ChunkChallenge(root.clone(), minter);
+        let challenge: ChunkChallenge = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(VerificationError::NotFound)?;
+        challenge.minter.require_auth();
+        env.storage().temporary().remove(&key);
+
+        let commitment = challenge.commitment;
+        let answered = proofs.len() == challenge.indices.len()
+            && proofs.iter().zip(challenge.indices.iter()).all(|(proof, index)| {
+                proof.index == index && Self::verify_chunk(&env, &commitment, &proof)
+            });
+
+        let content_hash = Self::hex_string(&env, &root.to_array());
+        if !answered {
+            return Ok(VerificationResult {
+                success: false,
+                content_hash,
+                hash_algorithm: HashAlgorithm::Sha256,
+                certificate_id: None,
+                state: RequestState::Rejected(RejectionReason::ChunkProofInvalid),
+            });
+        }
+
+        Ok(Self::complete_verification(
+            &env,
+            true,
+            content_hash,
+            HashAlgorithm::Sha256,
+            ContentLayout::Chunked(commitment),
+            &challenge.owner,
+        ))
+    }
+
+    /// Return `minter`'s open chunk-sampling challenge for a Merkle root, if any.
+    pub fn get_chunk_challenge(
+        env: Env,
+        root: BytesN<32>,
+        minter: Address,
+    ) -> Option<ChunkChallenge> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::ChunkChallenge(root, minter))
+    }
+
+    /// Check one chunk's size and Merkle inclusion proof against `commitment`.
+    fn verify_chunk(env: &Env, commitment: &MerkleCommitment, proof: &ChunkProof) -> bool {
+        if proof.index >= commitment.chunk_count {
+            return false;
+        }
+        let len = proof.chunk.len();
+        let size_ok = if proof.index + 1 == commitment.chunk_count {
+            len > 0 && len <= commitment.chunk_size
+        } else {
+            len == commitment.chunk_size
+        };
+        if !size_ok {
+            return false;
+        }
+
+        let mut leaf = Bytes::from_array(env, &[0x00]);
+        leaf.append(&proof.chunk);
+        let mut hash: BytesN<32> = env.crypto().sha256(&leaf).into();
+
+        let mut siblings = proof.siblings.iter();
+        let mut index = proof.index;
+        let mut width = commitment.chunk_count;
+        while width > 1 {
+            let (left, right) = if index % 2 == 1 {
+                match siblings.next() {
+                    Some(sibling) => (sibling, hash),
+                    None => return false,
+                }
+            } else if index + 1 < width {
+                match siblings.next() {
+                    Some(sibling) => (hash, sibling),
+                    None => return false,
+                }
+            } else {
+                // Last node of an odd-width level is promoted unchanged.
+                index /= 2;
+                width = width.div_ceil(2);
+                continue;
+            };
+            let mut node = Bytes::from_array(env, &[0x01]);
+            node.append(&left.into());
+            node.append(&right.into());
+            hash = env.crypto().sha256(&node).into();
+            index /= 2;
+            width = width.div_ceil(2);
+        }
+
+        siblings.next().is_none() && hash == commitment.root
+    }
+
+    /// Enable or disable acceptance of legacy 16-character truncated hashes
+    /// in `verify_and_mint`. Only the admin may call this function.
+    pub fn set_legacy_hash_mode(env: Env, enabled: bool) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::LegacyHashMode, &enabled);
+        Ok(())
+    }
+
+    /// Return whether legacy truncated hashes are accepted. Disabled by default.
+    pub fn get_legacy_hash_mode(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::LegacyHashMode)
+            .unwrap_or(false)
+    }
+
+    /// Check `content` against a caller-supplied expected hash string.
+    fn verify_expected(
+        env: &Env,
+        content: &Bytes,
+        expected_hash: &String,
+        owner: &Address,
+    ) -> Result<VerificationResult, VerificationError> {
+        match Self::parse_expected_hash(expected_hash) {
+            Some(ExpectedHash::Full(algorithm, expected)) => {
+                Ok(Self::verify_digest(env, content, algorithm, expected, owner))
+            }
+            // Legacy certificates were minted with the truncated digest as
+            // their manifest hash, so keep using it to detect duplicates.
+            Some(ExpectedHash::Legacy(expected))
+                if Self::get_legacy_hash_mode(env.clone()) =>
+            {
+                let digest = env.crypto().sha256(content).to_array();
+                let truncated = &digest[..LEGACY_HASH_BYTES];
+                Ok(Self::complete_verification(
+                    env,
+                    expected == truncated,
+                    Self::hex_string(env, truncated),
+                    HashAlgorithm::Sha256,
+                    ContentLayout::Whole,
+                    owner,
+                ))
+            }
+            _ => Err(VerificationError::InvalidHash),
+        }
+    }
+
+    /// Recompute the content digest with `algorithm` and compare it to `expected`.
+    fn verify_digest(
+        env: &Env,
+        content: &Bytes,
+        algorithm: HashAlgorithm,
+        expected: [u8; 32],
+        owner: &Address,
+    ) -> VerificationResult {
+        match Self::compute_hash(env, algorithm, content) {
+            Some(digest) => Self::complete_verification(
+                env,
+                expected == digest,
+                Self::hex_string(env, &digest),
+                algorithm,
+                ContentLayout::Whole,
+                owner,
+            ),
+            None => VerificationResult {
+                success: false,
+                content_hash: String::from_str(env, ""),
+                hash_algorithm: algorithm,
+                certificate_id: None,
+                state: RequestState::Rejected(RejectionReason::UnsupportedAlgorithm),
+            },
+        }
+    }
+
+    /// Mint on a matching hash and build the verification result.
+    fn complete_verification(
+        env: &Env,
+        matched: bool,
+        content_hash: String,
+        hash_algorithm: HashAlgorithm,
+        layout: ContentLayout,
+        owner: &Address,
+    ) -> VerificationResult {
+        if !matched {
+            // Verification failed - return without minting
+            return VerificationResult {
+                success: false,
+                content_hash,
+                hash_algorithm,
+                certificate_id: None,
+                state: RequestState::Rejected(RejectionReason::HashMismatch),
+            };
+        }
+        
+        // An earlier claim by someone else takes priority over this mint
+        if let Some(claim) = Self::get_claim(env.clone(), content_hash.clone()) {
+            if &claim.owner != owner {
+                return VerificationResult {
+                    success: false,
+                    content_hash,
+                    hash_algorithm,
+                    certificate_id: None,
+                    state: RequestState::Rejected(RejectionReason::ClaimConflict),
+                };
+            }
+        }
+
+        // Verification succeeded - mint provenance certificate
+        let details = provenance::CertificateDetails {
+            storage_id: String::from_str(env, "unknown"),
+            manifest_hash: content_hash.clone(),
+            attestation_hash: String::from_str(env, ""),
+            hash_algorithm,
+            layout,
+            job: provenance::JobResult::ContentIntegrity,
+            job_digest: BytesN::from_array(env, &[0; 32]),
+        };
+        let certificate_id = Self::mint_certificate(env, &details, owner);
+        
+        match certificate_id {
+            Ok(cert_id) => {
+                VerificationResult {
+                    success: true,
+                    content_hash,
+                    hash_algorithm,
+                    certificate_id: Some(cert_id),
+                    state: RequestState::Verified,
+                }
+            }
+            Err(_) => {
+                // Minting failed but verification succeeded
+                // Handle error gracefully and return success without certificate
+                VerificationResult {
+                    success: true,
+                    content_hash,
+                    hash_algorithm,
+                    certificate_id: None,
+                    state: RequestState::Failed,
+                }
+            }
+        }
+    }
+    
+    /// Compute the digest of the content's bytes with `algorithm`.
+    /// Returns `None` for algorithms the host cannot compute.
+    fn compute_hash(env: &Env, algorithm: HashAlgorithm, content: &Bytes) -> Option<[u8; 32]> {
+        match algorithm {
+            HashAlgorithm::Sha256 => Some(env.crypto().sha256(content).to_array()),
+            HashAlgorithm::Keccak256 => Some(env.crypto().keccak256(content).to_array()),
+            HashAlgorithm::Blake3 | HashAlgorithm::Blake2b256 => None,
+        }
+    }
+
+    /// Lower-case hex encoding of up to 32 bytes
+    fn hex_string(env: &Env, bytes: &[u8]) -> String {
+        let hex_chars: [u8; 16] = [
+            b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7',
+            b'8', b'9', b'a', b'b', b'c', b'd', b'e', b'f',
         ];
         
-        let mut result_bytes: [u8; 16] = [0; 16];
-        for i in 0..8 {
-            let byte = hash_array[i];
+        let mut result_bytes: [u8; 64] = [0; 64];
+        for (i, byte) in bytes.iter().enumerate() {
             result_bytes[i * 2] = hex_chars[(byte >> 4) as usize];
             result_bytes[i * 2 + 1] = hex_chars[(byte & 0x0f) as usize];
         }
-        
-        // Convert byte array to String
-        String::from_bytes(env, &result_bytes)
+        
+        String::from_bytes(env, &result_bytes[..bytes.len() * 2])
+    }
+
+    /// Parse a caller-supplied expected hash into a full or legacy digest.
+    /// Returns `None` for anything that is not one of the accepted forms.
+    fn parse_expected_hash(expected: &String) -> Option<ExpectedHash> {
+        let len = expected.len() as usize;
+        if len > MAX_EXPECTED_HASH_LEN {
+            return None;
+        }
+        let mut buf = [0u8; MAX_EXPECTED_HASH_LEN];
+        expected.copy_into_slice(&mut buf[..len]);
+
+        let mut text = &buf[..len];
+        let mut algorithm = HashAlgorithm::Sha256;
+        if text.starts_with(b"0x") || text.starts_with(b"0X") {
+            text = &text[2..];
+        } else if let Some((prefix, tagged)) = MULTIHASH_PREFIXES
+            .iter()
+            .find(|(prefix, _)| text.len() == prefix.len() + 64 && text.starts_with(prefix))
+        {
+            text = &text[prefix.len()..];
+            algorithm = *tagged;
+        }
+
+        match text.len() {
+            64 => {
+                let mut digest = [0u8; 32];
+                Self::decode_hex(text, &mut digest)?;
+                Some(ExpectedHash::Full(algorithm, digest))
+            }
+            16 => {
+                let mut digest = [0u8; LEGACY_HASH_BYTES];
+                Self::decode_hex(text, &mut digest)?;
+                Some(ExpectedHash::Legacy(digest))
+            }
+            _ => None,
+        }
+    }
+
+    fn decode_hex(text: &[u8], out: &mut [u8]) -> Option<()> {
+        fn nibble(c: u8) -> Option<u8> {
+            match c {
+                b'0'..=b'9' => Some(c - b'0'),
+                b'a'..=b'f' => Some(c - b'a' + 10),
+                b'A'..=b'F' => Some(c - b'A' + 10),
+                _ => None,
+            }
+        }
+        for (i, pair) in text.chunks(2).enumerate() {
+            out[i] = (nibble(pair[0])? << 4) | nibble(pair[1])?;
+        }
+        Some(())
+    }
+    
+    /// Call provenance contract to mint certificate
+    fn mint_certificate(
+        env: &Env,
+        details: &provenance::CertificateDetails,
+        owner: &Address,
+    ) -> Result<u64, ()> {
+        // Get provenance contract address
+        let provenance_addr: Address = match env.storage().instance().get(&DataKey::Provenance) {
+            Some(addr) => addr,
+            None => return Err(()),
+        };
+        
+        // Create provenance contract client
+        let provenance_client = provenance::Client::new(env, &provenance_addr);
+        
+        // Call mint function with error handling
+        let minter = env.current_contract_address();
+        let certificate_id = match provenance_client.try_mint(&minter, owner, details) {
+            Ok(Ok(id)) => id,
+            _ => return Err(()),
+        };
+
+        // Remember who minted the hash so a prior claim can contest it
+        env.storage().persistent().set(
+            &DataKey::Minted(details.manifest_hash.clone()),
+            &MintRecord {
+                owner: owner.clone(),
+                certificate_id,
+                minted_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(certificate_id)
+    }
+
+    /// Add a trusted TEE measurement hash to the registry.
+    /// Only the admin may call this function.
+    /// Emits a `TeeHashAdded` event on success.
+    /// Returns `DuplicateHash` error if the hash already exists.
+    pub fn add_tee_hash(env: Env, hash: BytesN<32>) -> Result<(), VerificationError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VerificationError::Unauthorized)?;
+        admin.require_auth();
+
+        // Check if hash already exists
+        let exists: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TeeHash(hash.clone()))
+            .unwrap_or(false);
+
+        if exists {
+            return Err(VerificationError::DuplicateHash);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::TeeHash(hash.clone()), &true);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "TeeHashAdded"),
+                hash.clone(),
+            ),
+            TeeHashEventData { hash: hash.clone() },
+        );
+
+        Ok(())
+    }
+
+    /// Remove a TEE hash from the registry.
+    /// Only the admin may call this function.
+    pub fn remove_tee_hash(env: Env, hash: BytesN<32>) -> Result<(), VerificationError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VerificationError::Unauthorized)?;
+        admin.require_auth();
+
+        env.storage().persistent().remove(&DataKey::TeeHash(hash.clone()));
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "TeeHashRemoved"),
+                hash.clone(),
+            ),
+            TeeHashEventData {
+                hash: hash.clone(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Return whether `hash` is registered as a trusted TEE measurement.
+    pub fn has_tee_hash(env: Env, hash: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TeeHash(hash))
+            .unwrap_or(false)
+    }
+
+    /// Public verification helper used by external contracts (e.g. the Oracle).
+    ///
+    /// Returns `true` if the provided TEE measurement hash is currently
+    /// registered as trusted in the registry, `false` otherwise.
+    pub fn is_hash_verified(env: Env, hash: BytesN<32>) -> bool {
+        Self::has_tee_hash(env, hash)
+    }
+
+    /// Add an authorized Oracle provider to the registry.
+    /// Only the admin may call this function.
+    pub fn add_provider(env: Env, provider: BytesN<32>) -> Result<(), VerificationError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VerificationError::Unauthorized)?;
+        admin.require_auth();
+
+        env.storage().persistent().set(&DataKey::Provider(provider.clone()), &true);
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "ProviderAdded"),
+                provider.clone(),
+            ),
+            ProviderEventData {
+                provider: provider.clone(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Remove an Oracle provider from the registry.
+    /// Only the admin may call this function.
+    pub fn remove_provider(env: Env, provider: BytesN<32>) -> Result<(), VerificationError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VerificationError::Unauthorized)?;
+        admin.require_auth();
+
+        if let Some(record) = Self::get_provider(env.clone(), provider.clone()) {
+            Self::unindex_operator(&env, &record.metadata.operator, &provider);
+        }
+        env.storage().persistent().remove(&DataKey::Provider(provider.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ProviderRecord(provider.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Suspension(provider.clone()));
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "ProviderRemoved"),
+                provider.clone(),
+            ),
+            ProviderEventData {
+                provider: provider.clone(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Submit a provider application for admin review.
+    ///
+    /// The `operator` named in `metadata` must authorize the call, and
+    /// `signature` must be an Ed25519 signature by `key` over the XDR
+    /// encoding of the matching `ProviderApplication`. A key that is already
+    /// trusted, or that has a pending or approved application, returns
+    /// `ProviderExists`; a rejected applicant may apply again.
+    /// Emits a `ProviderApplied` event on success.
+    pub fn apply(
+        env: Env,
+        key: BytesN<32>,
+        metadata: ProviderMetadata,
+        signature: BytesN<64>,
+    ) -> Result<(), VerificationError> {
+        metadata.operator.require_auth();
+        Self::validate_metadata(&metadata)?;
+
+        if env.storage().persistent().has(&DataKey::Provider(key.clone())) {
+            return Err(VerificationError::ProviderExists);
+        }
+        if let Some(existing) = Self::get_provider(env.clone(), key.clone()) {
+            if existing.status != ProviderStatus::Rejected {
+                return Err(VerificationError::ProviderExists);
+            }
+        }
+
+        let application = ProviderApplication {
+            registry: env.current_contract_address(),
+            key: key.clone(),
+            operator: metadata.operator.clone(),
+        };
+        env.crypto()
+            .ed25519_verify(&key, &application.to_xdr(&env), &signature);
+
+        let now = env.ledger().timestamp();
+        let record = ProviderRecord {
+            key: key.clone(),
+            metadata,
+            status: ProviderStatus::Pending,
+            applied_at: now,
+            updated_at: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProviderRecord(key.clone()), &record);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "ProviderApplied"),
+                key.clone(),
+            ),
+            ProviderEventData { provider: key },
+        );
+
+        Ok(())
+    }
+
+    /// Approve a pending provider application, making the key trusted.
+    /// Only the admin may call this function.
+    pub fn approve_provider(env: Env, key: BytesN<32>) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
+
+        let mut record = Self::pending_application(&env, &key)?;
+        record.status = ProviderStatus::Approved;
+        record.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProviderRecord(key.clone()), &record);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Provider(key.clone()), &true);
+        Self::index_operator(&env, &record.metadata.operator, &key);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "ProviderApproved"),
+                key.clone(),
+            ),
+            ProviderEventData { provider: key },
+        );
+
+        Ok(())
+    }
+
+    /// Reject a pending provider application. The record is kept so the
+    /// decision stays visible through `get_provider`.
+    /// Only the admin may call this function.
+    pub fn reject_provider(env: Env, key: BytesN<32>) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
+
+        let mut record = Self::pending_application(&env, &key)?;
+        record.status = ProviderStatus::Rejected;
+        record.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProviderRecord(key.clone()), &record);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "ProviderRejected"),
+                key.clone(),
+            ),
+            ProviderEventData { provider: key },
+        );
+
+        Ok(())
+    }
+
+    /// Replace the metadata of an approved provider.
+    ///
+    /// The current operator must authorize the call; when the operator is
+    /// being changed, the new operator must authorize it as well.
+    pub fn update_provider(
+        env: Env,
+        key: BytesN<32>,
+        metadata: ProviderMetadata,
+    ) -> Result<(), VerificationError> {
+        let mut record = Self::get_provider(env.clone(), key.clone())
+            .ok_or(VerificationError::ProviderNotFound)?;
+        if record.status != ProviderStatus::Approved {
+            return Err(VerificationError::InvalidProviderState);
+        }
+
+        record.metadata.operator.require_auth();
+        if metadata.operator != record.metadata.operator {
+            metadata.operator.require_auth();
+        }
+        Self::validate_metadata(&metadata)?;
+
+        if metadata.operator != record.metadata.operator {
+            Self::unindex_operator(&env, &record.metadata.operator, &key);
+            Self::index_operator(&env, &metadata.operator, &key);
+        }
+        record.metadata = metadata;
+        record.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProviderRecord(key.clone()), &record);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "ProviderUpdated"),
+                key.clone(),
+            ),
+            ProviderEventData { provider: key },
+        );
+
+        Ok(())
+    }
+
+    /// Suspend a trusted provider until `until_ledger`.
+    /// Only the admin may call this function.
+    ///
+    /// Unlike `remove_provider`, the provider keeps its trust entry, metadata,
+    /// signing keys and TEE bindings; `is_verified` and `process_verification`
+    /// reject it until the suspension lapses or `reinstate_provider` is called.
+    /// Suspending an already suspended provider replaces the suspension.
+    pub fn suspend_provider(
+        env: Env,
+        key: BytesN<32>,
+        until_ledger: u32,
+        reason: String,
+    ) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
+
+        if !env.storage().persistent().has(&DataKey::Provider(key.clone())) {
+            return Err(VerificationError::ProviderNotFound);
+        }
+        if until_ledger <= env.ledger().sequence() {
+            return Err(VerificationError::InvalidSuspension);
+        }
+
+        let suspension = Suspension {
+            until_ledger,
+            reason: reason.clone(),
+            suspended_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Suspension(key.clone()), &suspension);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "ProviderSuspended"),
+                key.clone(),
+            ),
+            SuspensionEventData {
+                provider: key,
+                until_ledger,
+                reason,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Lift a provider's suspension before it lapses.
+    /// Only the admin may call this function.
+    pub fn reinstate_provider(env: Env, key: BytesN<32>) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
+
+        if Self::get_suspension(env.clone(), key.clone()).is_none() {
+            return Err(VerificationError::InvalidProviderState);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Suspension(key.clone()));
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "ProviderReinstated"),
+                key.clone(),
+            ),
+            ProviderEventData { provider: key },
+        );
+
+        Ok(())
+    }
+
+    /// Return the provider's suspension while it is in effect.
+    pub fn get_suspension(env: Env, key: BytesN<32>) -> Option<Suspension> {
+        env.storage()
+            .persistent()
+            .get::<_, Suspension>(&DataKey::Suspension(key))
+            .filter(|suspension| suspension.until_ledger > env.ledger().sequence())
+    }
+
+    /// Return whether the provider is currently suspended.
+    pub fn is_suspended(env: Env, key: BytesN<32>) -> bool {
+        Self::get_suspension(env, key).is_some()
+    }
+
+    /// Return whether `operator` manages at least one approved provider that
+    /// is still trusted, not suspended and not declared compromised.
+    ///
+    /// The oracle only assigns work to operators this reports as eligible.
+    pub fn is_eligible_operator(env: Env, operator: Address) -> bool {
+        Self::operator_keys(&env, &operator).iter().any(|key| {
+            env.storage().persistent().has(&DataKey::Provider(key.clone()))
+                && !Self::is_suspended(env.clone(), key.clone())
+                && !Self::is_compromised(&env, &key)
+        })
+    }
+
+    /// Return whether the signing key `key`, a provider's registered key or
+    /// one rotated in since, belongs to a provider `operator` manages.
+    ///
+    /// The oracle only accepts attestations signed by its assigned
+    /// operators' keys.
+    pub fn is_operator_key(env: Env, operator: Address, key: BytesN<32>) -> bool {
+        let provider = Self::get_signing_key(env.clone(), key.clone())
+            .map(|entry| entry.provider)
+            .unwrap_or(key);
+        Self::operator_keys(&env, &operator).contains(&provider)
+    }
+
+    fn operator_keys(env: &Env, operator: &Address) -> Vec<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OperatorKeys(operator.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn index_operator(env: &Env, operator: &Address, key: &BytesN<32>) {
+        let mut keys = Self::operator_keys(env, operator);
+        if !keys.contains(key) {
+            keys.push_back(key.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::OperatorKeys(operator.clone()), &keys);
+        }
     }
-    
-    /// Call provenance contract to mint certificate
-    fn mint_certificate(env: &Env, content_hash: &String, owner: &Address) -> Result<u64, ()> {
-        // Get provenance contract address
-        let provenance_addr: Address = match env.storage().instance().get(&DataKey::Provenance) {
-            Some(addr) => addr,
-            None => return Err(()),
+
+    fn unindex_operator(env: &Env, operator: &Address, key: &BytesN<32>) {
+        let mut keys = Self::operator_keys(env, operator);
+        if let Some(index) = keys.first_index_of(key) {
+            keys.remove(index);
+            let storage_key = DataKey::OperatorKeys(operator.clone());
+            if keys.is_empty() {
+                env.storage().persistent().remove(&storage_key);
+            } else {
+                env.storage().persistent().set(&storage_key, &keys);
+            }
+        }
+    }
+
+    /// Declare that the signing key `provider` was compromised from
+    /// `since_timestamp` onwards. Only the admin may call this function.
+    ///
+    /// The key is refused for new attestations, and requests it verified at
+    /// or after `since_timestamp` are reported by `is_request_suspect`.
+    /// Declaring again can only move the start of the window earlier.
+    /// Emits a `KeyCompromised` event.
+    pub fn declare_compromise(
+        env: Env,
+        provider: BytesN<32>,
+        since_timestamp: u64,
+    ) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
+
+        let owner = Self::get_signing_key(env.clone(), provider.clone())
+            .map(|entry| entry.provider)
+            .unwrap_or(provider.clone());
+        if !env.storage().persistent().has(&DataKey::Provider(owner.clone())) {
+            return Err(VerificationError::ProviderNotFound);
+        }
+        let now = env.ledger().timestamp();
+        if since_timestamp > now {
+            return Err(VerificationError::InvalidCompromise);
+        }
+
+        let since = match Self::get_compromise(env.clone(), provider.clone()) {
+            Some(existing) if existing.since < since_timestamp => existing.since,
+            _ => since_timestamp,
         };
-        
-        // Create provenance contract client
-        let provenance_client = provenance::Client::new(env, &provenance_addr);
-        
-        let details = provenance::CertificateDetails {
-            storage_id: String::from_str(env, "unknown"),
-            manifest_hash: content_hash.clone(),
-            attestation_hash: String::from_str(env, ""),
+        let compromise = Compromise {
+            provider: owner.clone(),
+            since,
+            declared_at: now,
         };
-        
-        // Call mint function with error handling
-        match provenance_client.try_mint(owner, &details) {
-            Ok(Ok(id)) => Ok(id),
-            _ => Err(()),
+        env.storage()
+            .persistent()
+            .set(&DataKey::Compromise(provider.clone()), &compromise);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, "registry"),
+                soroban_sdk::Symbol::new(&env, "KeyCompromised"),
+                owner.clone(),
+            ),
+            CompromiseEventData {
+                provider: owner,
+                key: provider,
+                since,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Return the declared compromise of a signing key, if any.
+    pub fn get_compromise(env: Env, key: BytesN<32>) -> Option<Compromise> {
+        env.storage().persistent().get(&DataKey::Compromise(key))
+    }
+
+    /// Return whether a verified request was attested by a key that was
+    /// already compromised at verification time.
+    pub fn is_request_suspect(env: Env, request_id: u64) -> bool {
+        let req: VerificationRequest = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::Request(request_id))
+        {
+            Some(req) => req,
+            None => return false,
+        };
+        match (req.verified_by, req.verified_at) {
+            (Some(key), Some(verified_at)) => Self::get_compromise(env, key)
+                .map(|compromise| verified_at >= compromise.since)
+                .unwrap_or(false),
+            _ => false,
         }
     }
 
-    /// Add a trusted TEE measurement hash to the registry.
+    /// Flag the provenance certificate issued for a suspect request.
     /// Only the admin may call this function.
-    /// Emits a `TeeHashAdded` event on success.
-    /// Returns `DuplicateHash` error if the hash already exists.
-    pub fn add_tee_hash(env: Env, hash: BytesN<32>) -> Result<(), VerificationError> {
-        let admin: Address = env
+    ///
+    /// Returns `NotSuspect` unless `is_request_suspect` holds for `request_id`,
+    /// and `NotFound` if no certificate was recorded for the request.
+    pub fn flag_suspect_certificate(env: Env, request_id: u64) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
+
+        if !Self::is_request_suspect(env.clone(), request_id) {
+            return Err(VerificationError::NotSuspect);
+        }
+        let certificate_id = Self::get_request(env.clone(), request_id)
+            .and_then(|req| req.certificate_id)
+            .ok_or(VerificationError::NotFound)?;
+
+        let provenance_addr: Address = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .ok_or(VerificationError::Unauthorized)?;
-        admin.require_auth();
+            .get(&DataKey::Provenance)
+            .ok_or(VerificationError::ProvenanceCallFailed)?;
+        let provenance_client = provenance::Client::new(&env, &provenance_addr);
+        match provenance_client.try_flag_certificate(
+            &env.current_contract_address(),
+            &certificate_id,
+            &String::from_str(&env, "KeyCompromised"),
+        ) {
+            Ok(Ok(())) => Ok(()),
+            _ => Err(VerificationError::ProvenanceCallFailed),
+        }
+    }
 
-        // Check if hash already exists
-        let exists: bool = env
-            .storage()
-            .persistent()
-            .get(&DataKey::TeeHash(hash.clone()))
-            .unwrap_or(false);
+    /// Return whether the signing key is inside a declared compromise window.
+    fn is_compromised(env: &Env, key: &BytesN<32>) -> bool {
+        Self::get_compromise(env.clone(), key.clone())
+            .map(|compromise| env.ledger().timestamp() >= compromise.since)
+            .unwrap_or(false)
+    }
 
-        if exists {
-            return Err(VerificationError::DuplicateHash);
-        }
+    /// Allow `provider` to present the TEE measurement `hash`.
+    /// Only the admin may call this function.
+    ///
+    /// Both the provider and the hash must still be trusted on their own;
+    /// this binding only narrows which trusted measurements a trusted
+    /// provider may present.
+    pub fn allow_provider_tee(
+        env: Env,
+        provider: BytesN<32>,
+        hash: BytesN<32>,
+    ) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::TeeHash(hash.clone()), &true);
+        env.storage().persistent().set(
+            &DataKey::ProviderTee(provider.clone(), hash.clone()),
+            &true,
+        );
 
         #[allow(deprecated)]
         env.events().publish(
             (
                 soroban_sdk::Symbol::new(&env, "registry"),
-                soroban_sdk::Symbol::new(&env, "TeeHashAdded"),
-                hash.clone(),
+                soroban_sdk::Symbol::new(&env, "ProviderTeeAllowed"),
+                provider.clone(),
             ),
-            TeeHashEventData { hash: hash.clone() },
+            ProviderTeeEventData { provider, hash },
         );
 
         Ok(())
     }
 
-    /// Remove a TEE hash from the registry.
+    /// Remove the binding between `provider` and the TEE measurement `hash`.
     /// Only the admin may call this function.
-    pub fn remove_tee_hash(env: Env, hash: BytesN<32>) -> Result<(), VerificationError> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(VerificationError::Unauthorized)?;
-        admin.require_auth();
+    pub fn disallow_provider_tee(
+        env: Env,
+        provider: BytesN<32>,
+        hash: BytesN<32>,
+    ) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
 
-        env.storage().persistent().remove(&DataKey::TeeHash(hash.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ProviderTee(provider.clone(), hash.clone()));
 
         #[allow(deprecated)]
         env.events().publish(
             (
                 soroban_sdk::Symbol::new(&env, "registry"),
-                soroban_sdk::Symbol::new(&env, "TeeHashRemoved"),
-                hash.clone(),
+                soroban_sdk::Symbol::new(&env, "ProviderTeeDisallowed"),
+                provider.clone(),
             ),
-            TeeHashEventData {
-                hash: hash.clone(),
-            },
+            ProviderTeeEventData { provider, hash },
         );
 
         Ok(())
     }
 
-    /// Return whether `hash` is registered as a trusted TEE measurement.
-    pub fn has_tee_hash(env: Env, hash: BytesN<32>) -> bool {
+    /// Return whether `provider` is allowed to present the TEE measurement `hash`.
+    pub fn is_provider_tee_allowed(env: Env, provider: BytesN<32>, hash: BytesN<32>) -> bool {
         env.storage()
             .persistent()
-            .get(&DataKey::TeeHash(hash))
+            .get(&DataKey::ProviderTee(provider, hash))
             .unwrap_or(false)
     }
 
-    /// Public verification helper used by external contracts (e.g. the Oracle).
+    /// Rotate a provider's signing key to `rotation.new_key`.
     ///
-    /// Returns `true` if the provided TEE measurement hash is currently
-    /// registered as trusted in the registry, `false` otherwise.
-    pub fn is_hash_verified(env: Env, hash: BytesN<32>) -> bool {
-        Self::has_tee_hash(env, hash)
-    }
-
-    /// Add an authorized Oracle provider to the registry.
-    /// Only the admin may call this function.
-    pub fn add_provider(env: Env, provider: BytesN<32>) -> Result<(), VerificationError> {
-        let admin: Address = env
+    /// `signature` must be an Ed25519 signature by `rotation.current_key` over
+    /// the XDR encoding of `rotation`, and `current_key` must be the provider's
+    /// active key and not declared compromised. A rotation naming another
+    /// registry fails with `InvalidSignature`. Both keys are accepted until
+    /// the overlap window elapses, after which the old key is retired
+    /// automatically. Returns the ledger sequence at which the old key
+    /// retires and emits a `KeyRotated` event.
+    pub fn rotate_key(
+        env: Env,
+        rotation: KeyRotation,
+        signature: BytesN<64>,
+    ) -> Result<u32, VerificationError> {
+        if rotation.registry != env.current_contract_address() {
+            return Err(VerificationError::InvalidSignature);
+        }
+        let provider = rotation.provider.clone();
+        if !env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(VerificationError::Unauthorized)?;
-        admin.require_auth();
+            .persistent()
+            .has(&DataKey::Provider(provider.clone()))
+        {
+            return Err(VerificationError::ProviderNotFound);
+        }
+        if rotation.current_key != Self::get_active_key(env.clone(), provider.clone()) {
+            return Err(VerificationError::KeyNotActive);
+        }
+        // Whoever leaked a compromised key must not be able to rotate the
+        // provider onto a key of their own.
+        if Self::is_compromised(&env, &rotation.current_key) {
+            return Err(VerificationError::KeyCompromised);
+        }
+        let new_key = rotation.new_key.clone();
+        if new_key == provider
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::ProviderKey(new_key.clone()))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::Provider(new_key.clone()))
+        {
+            return Err(VerificationError::KeyInUse);
+        }
+
+        let payload = rotation.clone().to_xdr(&env);
+        env.crypto()
+            .ed25519_verify(&rotation.current_key, &payload, &signature);
+
+        let retires_at = env
+            .ledger()
+            .sequence()
+            .saturating_add(Self::get_key_overlap(env.clone()));
+        env.storage().persistent().set(
+            &DataKey::ProviderKey(rotation.current_key.clone()),
+            &ProviderKey {
+                provider: provider.clone(),
+                retires_at: Some(retires_at),
+            },
+        );
+        env.storage().persistent().set(
+            &DataKey::ProviderKey(new_key.clone()),
+            &ProviderKey {
+                provider: provider.clone(),
+                retires_at: None,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveKey(provider.clone()), &new_key);
+
+        if let Some(mut record) = Self::get_provider(env.clone(), provider.clone()) {
+            record.metadata.signing_keys.push_back(new_key.clone());
+            record.updated_at = env.ledger().timestamp();
+            env.storage()
+                .persistent()
+                .set(&DataKey::ProviderRecord(provider.clone()), &record);
+        }
 
-        env.storage().persistent().set(&DataKey::Provider(provider.clone()), &true);
         #[allow(deprecated)]
         env.events().publish(
             (
                 soroban_sdk::Symbol::new(&env, "registry"),
-                soroban_sdk::Symbol::new(&env, "ProviderAdded"),
+                soroban_sdk::Symbol::new(&env, "KeyRotated"),
                 provider.clone(),
             ),
-            ProviderEventData {
-                provider: provider.clone(),
+            KeyRotatedEventData {
+                provider,
+                old_key: rotation.current_key,
+                new_key,
+                retires_at,
             },
         );
 
-        Ok(())
+        Ok(retires_at)
     }
 
-    /// Remove an Oracle provider from the registry.
+    /// Return the signing key `provider` must use for its next rotation.
+    /// Providers that never rotated sign with their provider key.
+    pub fn get_active_key(env: Env, provider: BytesN<32>) -> BytesN<32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ActiveKey(provider.clone()))
+            .unwrap_or(provider)
+    }
+
+    /// Return the rotation entry for a signing key, if it was ever rotated in or out.
+    pub fn get_signing_key(env: Env, key: BytesN<32>) -> Option<ProviderKey> {
+        env.storage().persistent().get(&DataKey::ProviderKey(key))
+    }
+
+    /// Set how many ledgers an old signing key stays valid after rotation.
     /// Only the admin may call this function.
-    pub fn remove_provider(env: Env, provider: BytesN<32>) -> Result<(), VerificationError> {
+    pub fn set_key_overlap(env: Env, ledgers: u32) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::KeyOverlap, &ledgers);
+        Ok(())
+    }
+
+    /// Return the configured key-rotation overlap window in ledgers.
+    pub fn get_key_overlap(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::KeyOverlap)
+            .unwrap_or(DEFAULT_KEY_OVERLAP_LEDGERS)
+    }
+
+    /// Resolve a signing key to the provider it belongs to.
+    ///
+    /// Keys without a rotation entry are their own provider. Returns `None`
+    /// once a rotated-out key has passed its retirement ledger.
+    fn resolve_signing_key(env: &Env, key: &BytesN<32>) -> Option<BytesN<32>> {
+        match Self::get_signing_key(env.clone(), key.clone()) {
+            Some(entry) => match entry.retires_at {
+                Some(retires_at) if env.ledger().sequence() >= retires_at => None,
+                _ => Some(entry.provider),
+            },
+            None => Some(key.clone()),
+        }
+    }
+
+    /// Return the application and metadata record for `key`, if any.
+    /// Providers added directly through `add_provider` have no record.
+    pub fn get_provider(env: Env, key: BytesN<32>) -> Option<ProviderRecord> {
+        env.storage().persistent().get(&DataKey::ProviderRecord(key))
+    }
+
+    /// Load the admin address and require its authorization.
+    fn require_admin(env: &Env) -> Result<Address, VerificationError> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
             .ok_or(VerificationError::Unauthorized)?;
         admin.require_auth();
+        Ok(admin)
+    }
 
-        env.storage().persistent().remove(&DataKey::Provider(provider.clone()));
-        #[allow(deprecated)]
-        env.events().publish(
-            (
-                soroban_sdk::Symbol::new(&env, "registry"),
-                soroban_sdk::Symbol::new(&env, "ProviderRemoved"),
-                provider.clone(),
-            ),
-            ProviderEventData {
-                provider: provider.clone(),
-            },
-        );
+    /// Load a provider record that is still awaiting an admin decision.
+    fn pending_application(
+        env: &Env,
+        key: &BytesN<32>,
+    ) -> Result<ProviderRecord, VerificationError> {
+        let record: ProviderRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProviderRecord(key.clone()))
+            .ok_or(VerificationError::ProviderNotFound)?;
+        if record.status != ProviderStatus::Pending {
+            return Err(VerificationError::InvalidProviderState);
+        }
+        Ok(record)
+    }
 
+    fn validate_metadata(metadata: &ProviderMetadata) -> Result<(), VerificationError> {
+        if metadata.name.is_empty() || metadata.signing_keys.is_empty() {
+            return Err(VerificationError::InvalidMetadata);
+        }
         Ok(())
     }
 
-    /// Setup helper: Create a pending request
-    pub fn create_request(env: Env, id: u64) {
+    /// Submit a verification request for `content_hash` and return its id.
+    ///
+    /// Ids are allocated sequentially from 1. The requester must authorize
+    /// the call and owns the certificate minted once the request verifies.
+    /// Submitted requests are the only ones `process_verification` accepts,
+    /// up to `get_request_ttl` ledgers after submission.
+    pub fn submit_request(
+        env: Env,
+        requester: Address,
+        content_hash: BytesN<32>,
+        hash_algorithm: HashAlgorithm,
+        storage_id: String,
+    ) -> Result<u64, VerificationError> {
+        requester.require_auth();
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRequestId)
+            .unwrap_or(1);
+        let key = DataKey::Request(id);
+        if env.storage().persistent().has(&key) {
+            return Err(VerificationError::RequestExists);
+        }
+
         let req = VerificationRequest {
             id,
+            requester: requester.clone(),
+            content_hash: content_hash.clone(),
+            hash_algorithm,
+            storage_id,
+            created_at: env.ledger().sequence(),
+            deadline: env
+                .ledger()
+                .sequence()
+                .saturating_add(Self::get_request_ttl(env.clone())),
             state: RequestState::Pending,
+            verified_by: None,
+            verified_at: None,
+            certificate_id: None,
         };
-        env.storage().persistent().set(&DataKey::Request(id), &req);
+        env.storage().persistent().set(&key, &req);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRequestId, &(id + 1));
+
+        RequestSubmitted {
+            request_id: id,
+            content_hash,
+            hash_algorithm,
+            requester,
+            deadline: req.deadline,
+        }
+        .publish(&env);
+        Ok(id)
     }
 
-    /// Get a request
+    /// Get a request, reporting a pending request past its deadline as
+    /// `Expired`. Returns `None` for ids that were never submitted.
     pub fn get_request(env: Env, id: u64) -> Option<VerificationRequest> {
-        env.storage().persistent().get(&DataKey::Request(id))
+        let mut req: VerificationRequest = env.storage().persistent().get(&DataKey::Request(id))?;
+        if req.state == RequestState::Pending && env.ledger().sequence() > req.deadline {
+            req.state = RequestState::Expired;
+        }
+        Some(req)
+    }
+
+    /// Withdraw a pending request.
+    ///
+    /// Only the requester may cancel, and only while the request is still
+    /// `Pending`. Later attestations for the id fail with `AlreadyProcessed`.
+    pub fn cancel_request(env: Env, request_id: u64) -> Result<(), VerificationError> {
+        let mut req =
+            Self::get_request(env.clone(), request_id).ok_or(VerificationError::NotFound)?;
+        req.requester.require_auth();
+        if req.state != RequestState::Pending {
+            return Err(VerificationError::AlreadyProcessed);
+        }
+
+        req.state = RequestState::Cancelled;
+        env.storage().persistent().set(&DataKey::Request(request_id), &req);
+
+        RequestCancelled {
+            request_id,
+            content_hash: req.content_hash,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Set how many ledgers new requests stay open.
+    /// Only the admin may call this function.
+    /// Returns `InvalidTtl` for zero or anything above `MAX_REQUEST_TTL_LEDGERS`.
+    pub fn set_request_ttl(env: Env, ledgers: u32) -> Result<(), VerificationError> {
+        Self::require_admin(&env)?;
+        if ledgers == 0 || ledgers > MAX_REQUEST_TTL_LEDGERS {
+            return Err(VerificationError::InvalidTtl);
+        }
+        env.storage().instance().set(&DataKey::RequestTtl, &ledgers);
+        Ok(())
+    }
+
+    /// Return the number of ledgers new requests stay open.
+    pub fn get_request_ttl(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RequestTtl)
+            .unwrap_or(DEFAULT_REQUEST_TTL_LEDGERS)
     }
 
     /// Core processing logic
@@ -376,22 +2158,45 @@ impl Registry {
             return Err(VerificationError::AlreadyProcessed);
         }
 
+        // 2.1 Requests past their deadline expire instead
+        if env.ledger().sequence() > req.deadline {
+            req.state = RequestState::Expired;
+            env.storage().persistent().set(&DataKey::Request(request_id), &req);
+            RequestExpired {
+                request_id,
+                content_hash: req.content_hash,
+            }
+            .publish(&env);
+            return Ok(req.state);
+        }
+
         // 3. Validate the signature ...
         let payload = attestation.clone().to_xdr(&env);
         env.crypto()
             .ed25519_verify(&attestation.provider, &payload, &signature);
 
-        // 4. Registry check
+        // 3.1 Keys declared compromised may not attest any more
+        if Self::is_compromised(&env, &attestation.provider) {
+            return Ok(Self::reject(&env, &mut req, RejectionReason::KeyCompromised));
+        }
+
+        // 4. Registry check, resolving rotated signing keys to their provider
+        let provider = match Self::resolve_signing_key(&env, &attestation.provider) {
+            Some(provider) => provider,
+            None => return Ok(Self::reject(&env, &mut req, RejectionReason::KeyRetired)),
+        };
         let is_authorized: bool = env
             .storage()
             .persistent()
-            .get(&DataKey::Provider(attestation.provider.clone()))
+            .get(&DataKey::Provider(provider.clone()))
             .unwrap_or(false);
 
         if !is_authorized {
-            req.state = RequestState::Rejected(String::from_str(&env, "Unauthorized"));
-            env.storage().persistent().set(&DataKey::Request(request_id), &req);
-            return Ok(req.state);
+            return Ok(Self::reject(&env, &mut req, RejectionReason::UnauthorizedProvider));
+        }
+
+        if Self::is_suspended(env.clone(), provider.clone()) {
+            return Ok(Self::reject(&env, &mut req, RejectionReason::ProviderSuspended));
         }
 
         // 4.1 TEE Hash check
@@ -402,41 +2207,101 @@ impl Registry {
             .unwrap_or(false);
 
         if !is_tee_authorized {
-            req.state = RequestState::Rejected(String::from_str(&env, "InvalidTeeHash"));
-            env.storage().persistent().set(&DataKey::Request(request_id), &req);
-            return Ok(req.state);
+            return Ok(Self::reject(&env, &mut req, RejectionReason::UntrustedTee));
+        }
+
+        // 4.2 Provider ↔ TEE binding check
+        if !Self::is_provider_tee_allowed(env.clone(), provider, attestation.tee_hash.clone()) {
+            return Ok(Self::reject(&env, &mut req, RejectionReason::ProviderTeeNotAllowed));
         }
 
         // 5. Attestation validation
         if attestation.request_id != request_id {
-            req.state = RequestState::Rejected(String::from_str(&env, "InvalidAttestation"));
-            env.storage().persistent().set(&DataKey::Request(request_id), &req);
-            return Ok(req.state);
+            return Ok(Self::reject(&env, &mut req, RejectionReason::AttestationMismatch));
         }
 
-        // If all checks pass: update request state to Verified and save
-        req.state = RequestState::Verified;
+        // 6. An earlier claim by someone else takes priority over this mint
+        let content_hash = Self::hex_string(&env, &req.content_hash.to_array());
+        if let Some(claim) = Self::get_claim(env.clone(), content_hash.clone()) {
+            if claim.owner != req.requester {
+                return Ok(Self::reject(&env, &mut req, RejectionReason::ClaimConflict));
+            }
+        }
+
+        // 7. Mint the certificate for the requester. The request is verified
+        // either way; a failed mint is recorded as `Failed`.
+        let details = provenance::CertificateDetails {
+            storage_id: req.storage_id.clone(),
+            manifest_hash: content_hash,
+            attestation_hash: Self::hex_string(&env, &env.crypto().sha256(&payload).to_array()),
+            hash_algorithm: req.hash_algorithm,
+            layout: ContentLayout::Whole,
+            job: provenance::JobResult::ContentIntegrity,
+            job_digest: BytesN::from_array(&env, &[0; 32]),
+        };
+        match Self::mint_certificate(&env, &details, &req.requester) {
+            Ok(certificate_id) => {
+                req.state = RequestState::Verified;
+                req.certificate_id = Some(certificate_id);
+            }
+            Err(()) => req.state = RequestState::Failed,
+        }
+        req.verified_by = Some(attestation.provider.clone());
+        req.verified_at = Some(env.ledger().timestamp());
         env.storage().persistent().set(&DataKey::Request(request_id), &req);
 
+        RequestVerified {
+            request_id,
+            content_hash: req.content_hash,
+            verified_by: attestation.provider,
+            certificate_id: req.certificate_id,
+        }
+        .publish(&env);
         Ok(req.state)
     }
 
+    /// Persist `req` as rejected for `reason` and return its new state.
+    fn reject(env: &Env, req: &mut VerificationRequest, reason: RejectionReason) -> RequestState {
+        req.state = RequestState::Rejected(reason);
+        env.storage().persistent().set(&DataKey::Request(req.id), req);
+        RequestRejected {
+            request_id: req.id,
+            content_hash: req.content_hash.clone(),
+            reason,
+        }
+        .publish(env);
+        req.state.clone()
+    }
+
     /// Read-only function to verify if a hash and provider are trusted.
-    /// Returns true only if both the TEE hash and the provider are authorized.
+    /// Returns true only if both the TEE hash and the provider are authorized
+    /// and the provider is allowed to present that hash. `provider` may be any
+    /// of the provider's signing keys that has not yet been retired.
     pub fn is_verified(env: Env, hash: BytesN<32>, provider: BytesN<32>) -> bool {
+        if Self::is_compromised(&env, &provider) {
+            return false;
+        }
+        let provider = match Self::resolve_signing_key(&env, &provider) {
+            Some(provider) => provider,
+            None => return false,
+        };
+
         let is_tee_authorized = env
             .storage()
             .persistent()
-            .get(&DataKey::TeeHash(hash))
+            .get(&DataKey::TeeHash(hash.clone()))
             .unwrap_or(false);
 
         let is_auth_provider = env
             .storage()
             .persistent()
-            .get(&DataKey::Provider(provider))
+            .get(&DataKey::Provider(provider.clone()))
             .unwrap_or(false);
 
-        is_tee_authorized && is_auth_provider
+        is_tee_authorized
+            && is_auth_provider
+            && !Self::is_suspended(env.clone(), provider.clone())
+            && Self::is_provider_tee_allowed(env, provider, hash)
     }
 }
 
@@ -3,9 +3,9 @@ extern crate std;
 
 use super::*;
 use ed25519_dalek::{Signer, SigningKey};
-use soroban_sdk::testutils::Address as _;
+use soroban_sdk::testutils::{Address as _, Ledger as _};
 use soroban_sdk::xdr::ToXdr;
-use soroban_sdk::{Address, BytesN, Env};
+use soroban_sdk::{vec, Address, BytesN, Env, String};
 
 fn create_keypair(env: &Env, seed: u8) -> (SigningKey, BytesN<32>) {
     let secret = [seed; 32];
@@ -21,21 +21,55 @@ fn sign_payload(env: &Env, signing_key: &SigningKey, payload: &[u8]) -> BytesN<6
     BytesN::from_array(env, &sig_bytes)
 }
 
+/// Signs the XDR encoding of a contract value, as the contract verifies it.
+fn sign_xdr(env: &Env, signing_key: &SigningKey, payload: &soroban_sdk::Bytes) -> BytesN<64> {
+    let mut payload_buf = [0u8; 2048];
+    let len = payload.len() as usize;
+    payload.copy_into_slice(&mut payload_buf[..len]);
+    sign_payload(env, signing_key, &payload_buf[..len])
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
-/// Registers the contract, initialises it with a fresh admin, and returns
-/// `(client, admin_address)`.
-fn setup(env: &Env) -> (RegistryClient, Address) {
+/// Applies with `key`, signing the application with its secret half.
+fn apply(
+    env: &Env,
+    client: &RegistryClient,
+    signing_key: &SigningKey,
+    key: &BytesN<32>,
+    metadata: &ProviderMetadata,
+) {
+    let application = ProviderApplication {
+        registry: client.address.clone(),
+        key: key.clone(),
+        operator: metadata.operator.clone(),
+    };
+    let signature = sign_xdr(env, signing_key, &application.to_xdr(env));
+    client.apply(key, metadata, &signature);
+}
+
+/// Registers the contract, initialises it with a fresh admin and a mock
+/// provenance contract, and returns `(client, admin_address)`.
+fn setup(env: &Env) -> (RegistryClient<'_>, Address) {
     let contract_id = env.register(Registry, ());
     let client = RegistryClient::new(env, &contract_id);
     let admin = Address::generate(env);
-    let provenance = Address::generate(env);
+    let provenance = env.register(ProvenanceMock, ());
     client.init(&admin, &provenance);
     (client, admin)
 }
 
+/// Submits a request for an arbitrary content hash from a fresh requester.
+fn submit_request(env: &Env, client: &RegistryClient) -> u64 {
+    let requester = Address::generate(env);
+    // Distinct content per request, since provenance certifies a hash only once.
+    let content_hash: BytesN<32> = env.crypto().sha256(&requester.clone().to_xdr(env)).into();
+    let storage_id = String::from_str(env, "ipfs://content");
+    client.submit_request(&requester, &content_hash, &HashAlgorithm::Sha256, &storage_id)
+}
+
 // ---------------------------------------------------------------------------
 // Existing verification-flow tests (updated to use initialize + mock_all_auths)
 // ---------------------------------------------------------------------------
@@ -53,7 +87,8 @@ fn test_successful_verification() {
 
     client.add_provider(&pk);
     client.add_tee_hash(&tee_hash);
-    client.create_request(&1);
+    client.allow_provider_tee(&pk, &tee_hash);
+    assert_eq!(submit_request(&env, &client), 1);
 
     let attestation = Attestation {
         provider: pk.clone(),
@@ -91,7 +126,7 @@ fn test_invalid_signature() {
 
     client.add_provider(&pk);
     client.add_tee_hash(&tee_hash);
-    client.create_request(&1);
+    assert_eq!(submit_request(&env, &client), 1);
 
     let attestation = Attestation {
         provider: pk.clone(),
@@ -126,7 +161,7 @@ fn test_unauthorized_provider() {
     // DO NOT AUTHORIZE this provider
 
     client.add_tee_hash(&tee_hash);
-    client.create_request(&1);
+    assert_eq!(submit_request(&env, &client), 1);
 
     let attestation = Attestation {
         provider: pk.clone(),
@@ -147,16 +182,13 @@ fn test_unauthorized_provider() {
     let result = client.try_process_verification(&1, &attestation, &signature);
     assert_eq!(
         result,
-        Ok(Ok(RequestState::Rejected(soroban_sdk::String::from_str(
-            &env,
-            "Unauthorized"
-        ))))
+        Ok(Ok(RequestState::Rejected(RejectionReason::UnauthorizedProvider)))
     );
 
     let req = client.get_request(&1).unwrap();
     assert_eq!(
         req.state,
-        RequestState::Rejected(soroban_sdk::String::from_str(&env, "Unauthorized"))
+        RequestState::Rejected(RejectionReason::UnauthorizedProvider)
     );
 }
 
@@ -172,7 +204,7 @@ fn test_invalid_tee_hash() {
 
     client.add_provider(&pk);
     // DO NOT add the tee_hash used in the attestation
-    client.create_request(&1);
+    assert_eq!(submit_request(&env, &client), 1);
 
     let attestation = Attestation {
         provider: pk.clone(),
@@ -193,16 +225,13 @@ fn test_invalid_tee_hash() {
     let result = client.try_process_verification(&1, &attestation, &signature);
     assert_eq!(
         result,
-        Ok(Ok(RequestState::Rejected(soroban_sdk::String::from_str(
-            &env,
-            "InvalidTeeHash"
-        ))))
+        Ok(Ok(RequestState::Rejected(RejectionReason::UntrustedTee)))
     );
 
     let req = client.get_request(&1).unwrap();
     assert_eq!(
         req.state,
-        RequestState::Rejected(soroban_sdk::String::from_str(&env, "InvalidTeeHash"))
+        RequestState::Rejected(RejectionReason::UntrustedTee)
     );
 }
 
@@ -218,7 +247,8 @@ fn test_invalid_attestation() {
 
     client.add_provider(&pk);
     client.add_tee_hash(&tee_hash);
-    client.create_request(&1); // id is 1
+    client.allow_provider_tee(&pk, &tee_hash);
+    assert_eq!(submit_request(&env, &client), 1); // id is 1
 
     // Attestation claims id is 2
     let attestation = Attestation {
@@ -241,16 +271,13 @@ fn test_invalid_attestation() {
     let result = client.try_process_verification(&1, &attestation, &signature);
     assert_eq!(
         result,
-        Ok(Ok(RequestState::Rejected(soroban_sdk::String::from_str(
-            &env,
-            "InvalidAttestation"
-        ))))
+        Ok(Ok(RequestState::Rejected(RejectionReason::AttestationMismatch)))
     );
 
     let req = client.get_request(&1).unwrap();
     assert_eq!(
         req.state,
-        RequestState::Rejected(soroban_sdk::String::from_str(&env, "InvalidAttestation"))
+        RequestState::Rejected(RejectionReason::AttestationMismatch)
     );
 }
 
@@ -523,3 +550,1920 @@ fn test_remove_tee_hash_non_admin_panics() {
     // admin.require_auth() will abort — no auth is mocked for admin.
     client.remove_tee_hash(&hash);
 }
+
+// ---------------------------------------------------------------------------
+// Provider applications
+// ---------------------------------------------------------------------------
+
+fn sample_metadata(env: &Env, operator: &Address, signing_key: &BytesN<32>) -> ProviderMetadata {
+    ProviderMetadata {
+        name: String::from_str(env, "Acme Enclaves"),
+        contact_uri: String::from_str(env, "mailto:ops@acme.example"),
+        operator: operator.clone(),
+        enclave_types: vec![env, String::from_str(env, "sgx")],
+        signing_keys: vec![env, signing_key.clone()],
+    }
+}
+
+/// Apply → approve makes the provider trusted and readable via get_provider.
+#[test]
+fn test_apply_and_approve_provider() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, key) = create_keypair(&env, 3);
+    let tee_hash = BytesN::from_array(&env, &[4; 32]);
+    let operator = Address::generate(&env);
+    let metadata = sample_metadata(&env, &operator, &key);
+
+    apply(&env, &client, &sk, &key, &metadata);
+    let record = client.get_provider(&key).unwrap();
+    assert_eq!(record.status, ProviderStatus::Pending);
+    assert_eq!(record.metadata, metadata);
+
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&key, &tee_hash);
+    // A pending applicant is not yet trusted.
+    assert!(!client.is_verified(&tee_hash, &key));
+
+    client.approve_provider(&key);
+    let events_str = std::format!("{:#?}", soroban_sdk::testutils::Events::all(&env.events()));
+    assert!(events_str.contains("ProviderApproved"));
+
+    assert_eq!(
+        client.get_provider(&key).unwrap().status,
+        ProviderStatus::Approved
+    );
+    assert!(client.is_verified(&tee_hash, &key));
+}
+
+/// A rejected application stays visible and the provider remains untrusted.
+#[test]
+fn test_reject_provider_application() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, key) = create_keypair(&env, 3);
+    let operator = Address::generate(&env);
+
+    apply(
+        &env,
+        &client,
+        &sk,
+        &key,
+        &sample_metadata(&env, &operator, &key),
+    );
+    client.reject_provider(&key);
+
+    assert_eq!(
+        client.get_provider(&key).unwrap().status,
+        ProviderStatus::Rejected
+    );
+    assert_eq!(
+        client.try_approve_provider(&key),
+        Err(Ok(VerificationError::InvalidProviderState))
+    );
+
+    // Rejected applicants may apply again.
+    apply(
+        &env,
+        &client,
+        &sk,
+        &key,
+        &sample_metadata(&env, &operator, &key),
+    );
+    assert_eq!(
+        client.get_provider(&key).unwrap().status,
+        ProviderStatus::Pending
+    );
+}
+
+#[test]
+fn test_apply_twice_returns_provider_exists() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, key) = create_keypair(&env, 3);
+    let operator = Address::generate(&env);
+    let metadata = sample_metadata(&env, &operator, &key);
+
+    apply(&env, &client, &sk, &key, &metadata);
+    assert_eq!(
+        client.try_apply(&key, &metadata, &BytesN::from_array(&env, &[0; 64])),
+        Err(Ok(VerificationError::ProviderExists))
+    );
+
+    // Keys trusted directly by the admin cannot apply either.
+    let legacy = BytesN::from_array(&env, &[5; 32]);
+    client.add_provider(&legacy);
+    assert_eq!(
+        client.try_apply(
+            &legacy,
+            &sample_metadata(&env, &operator, &legacy),
+            &BytesN::from_array(&env, &[0; 64])
+        ),
+        Err(Ok(VerificationError::ProviderExists))
+    );
+}
+
+#[test]
+fn test_apply_rejects_empty_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (_, key) = create_keypair(&env, 3);
+    let operator = Address::generate(&env);
+    let mut metadata = sample_metadata(&env, &operator, &key);
+    metadata.name = String::from_str(&env, "");
+
+    assert_eq!(
+        client.try_apply(&key, &metadata, &BytesN::from_array(&env, &[0; 64])),
+        Err(Ok(VerificationError::InvalidMetadata))
+    );
+}
+
+/// Applying with a key the operator does not hold aborts, leaving the key
+/// free for its real holder.
+#[test]
+#[should_panic]
+fn test_apply_requires_key_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (_, key) = create_keypair(&env, 3);
+    let (squatter_sk, _) = create_keypair(&env, 4);
+    let operator = Address::generate(&env);
+
+    apply(
+        &env,
+        &client,
+        &squatter_sk,
+        &key,
+        &sample_metadata(&env, &operator, &key),
+    );
+}
+
+/// Approved providers update their own metadata; pending ones cannot.
+#[test]
+fn test_update_provider_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, key) = create_keypair(&env, 3);
+    let operator = Address::generate(&env);
+    let mut metadata = sample_metadata(&env, &operator, &key);
+
+    apply(&env, &client, &sk, &key, &metadata);
+    metadata.contact_uri = String::from_str(&env, "https://acme.example/oracle");
+    assert_eq!(
+        client.try_update_provider(&key, &metadata),
+        Err(Ok(VerificationError::InvalidProviderState))
+    );
+
+    client.approve_provider(&key);
+    client.update_provider(&key, &metadata);
+    assert_eq!(env.auths()[0].0, operator);
+    assert_eq!(client.get_provider(&key).unwrap().metadata, metadata);
+}
+
+#[test]
+fn test_remove_provider_deletes_record() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, key) = create_keypair(&env, 3);
+    let operator = Address::generate(&env);
+
+    apply(
+        &env,
+        &client,
+        &sk,
+        &key,
+        &sample_metadata(&env, &operator, &key),
+    );
+    client.approve_provider(&key);
+    client.remove_provider(&key);
+
+    assert_eq!(client.get_provider(&key), None);
+}
+
+/// Operators are eligible for oracle work only while one of their approved
+/// providers is trusted, unsuspended and uncompromised.
+#[test]
+fn test_is_eligible_operator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, key) = create_keypair(&env, 3);
+    let operator = Address::generate(&env);
+    let mut metadata = sample_metadata(&env, &operator, &key);
+
+    apply(&env, &client, &sk, &key, &metadata);
+    assert!(!client.is_eligible_operator(&operator));
+    client.approve_provider(&key);
+    assert!(client.is_eligible_operator(&operator));
+
+    client.suspend_provider(
+        &key,
+        &(env.ledger().sequence() + 10),
+        &String::from_str(&env, "audit"),
+    );
+    assert!(!client.is_eligible_operator(&operator));
+    client.reinstate_provider(&key);
+    assert!(client.is_eligible_operator(&operator));
+
+    // Handing the provider to a new operator moves the eligibility with it.
+    let successor = Address::generate(&env);
+    metadata.operator = successor.clone();
+    client.update_provider(&key, &metadata);
+    assert!(!client.is_eligible_operator(&operator));
+    assert!(client.is_eligible_operator(&successor));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.declare_compromise(&key, &1_000);
+    assert!(!client.is_eligible_operator(&successor));
+
+    client.remove_provider(&key);
+    assert!(!client.is_eligible_operator(&successor));
+}
+
+/// Signing keys, including rotated ones, resolve to their provider's operator.
+#[test]
+fn test_is_operator_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, key) = create_keypair(&env, 3);
+    let (_, new_key) = create_keypair(&env, 4);
+    let (_, stranger) = create_keypair(&env, 5);
+    let operator = Address::generate(&env);
+
+    apply(&env, &client, &sk, &key, &sample_metadata(&env, &operator, &key));
+    client.approve_provider(&key);
+    assert!(client.is_operator_key(&operator, &key));
+    assert!(!client.is_operator_key(&Address::generate(&env), &key));
+    assert!(!client.is_operator_key(&operator, &stranger));
+
+    rotate(&env, &client, &key, (&sk, &key), &new_key);
+    assert!(client.is_operator_key(&operator, &new_key));
+    assert!(client.is_operator_key(&operator, &key));
+}
+
+// ---------------------------------------------------------------------------
+// Provider ↔ TEE allow matrix
+// ---------------------------------------------------------------------------
+
+/// A trusted provider presenting another vendor's trusted measurement is not verified.
+#[test]
+fn test_is_verified_requires_provider_tee_binding() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let provider = BytesN::from_array(&env, &[1; 32]);
+    let vendor_a = BytesN::from_array(&env, &[0xA0; 32]);
+    let vendor_b = BytesN::from_array(&env, &[0xB0; 32]);
+
+    client.add_provider(&provider);
+    client.add_tee_hash(&vendor_a);
+    client.add_tee_hash(&vendor_b);
+    client.allow_provider_tee(&provider, &vendor_a);
+
+    assert!(client.is_verified(&vendor_a, &provider));
+    assert!(!client.is_verified(&vendor_b, &provider));
+
+    client.disallow_provider_tee(&provider, &vendor_a);
+    assert!(!client.is_provider_tee_allowed(&provider, &vendor_a));
+    assert!(!client.is_verified(&vendor_a, &provider));
+}
+
+#[test]
+fn test_process_verification_rejects_unbound_tee_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+
+    let (signing_key, pk) = create_keypair(&env, 1);
+    let vendor_a = BytesN::from_array(&env, &[0xA0; 32]);
+    let vendor_b = BytesN::from_array(&env, &[0xB0; 32]);
+
+    client.add_provider(&pk);
+    client.add_tee_hash(&vendor_a);
+    client.add_tee_hash(&vendor_b);
+    client.allow_provider_tee(&pk, &vendor_a);
+    assert_eq!(submit_request(&env, &client), 1);
+
+    let attestation = Attestation {
+        provider: pk.clone(),
+        tee_hash: vendor_b.clone(),
+        request_id: 1,
+    };
+
+    let payload = attestation.clone().to_xdr(&env);
+    let mut payload_buf = [0u8; 2048];
+    let payload_slice = {
+        let len = payload.len() as usize;
+        payload.copy_into_slice(&mut payload_buf[..len]);
+        &payload_buf[..len]
+    };
+
+    let signature = sign_payload(&env, &signing_key, payload_slice);
+
+    let result = client.try_process_verification(&1, &attestation, &signature);
+    assert_eq!(
+        result,
+        Ok(Ok(RequestState::Rejected(RejectionReason::ProviderTeeNotAllowed)))
+    );
+}
+
+#[test]
+fn test_allow_provider_tee_no_admin_returns_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Registry, ());
+    let client = RegistryClient::new(&env, &contract_id);
+    let provider = BytesN::from_array(&env, &[1; 32]);
+    let hash = BytesN::from_array(&env, &[2; 32]);
+
+    assert_eq!(
+        client.try_allow_provider_tee(&provider, &hash),
+        Err(Ok(VerificationError::Unauthorized))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Signing-key rotation
+// ---------------------------------------------------------------------------
+
+fn rotate(
+    env: &Env,
+    client: &RegistryClient,
+    provider: &BytesN<32>,
+    current: (&SigningKey, &BytesN<32>),
+    new_key: &BytesN<32>,
+) -> u32 {
+    let rotation = KeyRotation {
+        registry: client.address.clone(),
+        provider: provider.clone(),
+        current_key: current.1.clone(),
+        new_key: new_key.clone(),
+    };
+    let signature = sign_xdr(env, current.0, &rotation.clone().to_xdr(env));
+    client.rotate_key(&rotation, &signature)
+}
+
+fn attest(
+    env: &Env,
+    client: &RegistryClient,
+    request_id: u64,
+    key: (&SigningKey, &BytesN<32>),
+    tee_hash: &BytesN<32>,
+) -> RequestState {
+    let attestation = Attestation {
+        provider: key.1.clone(),
+        tee_hash: tee_hash.clone(),
+        request_id,
+    };
+    let signature = sign_xdr(env, key.0, &attestation.clone().to_xdr(env));
+    client.process_verification(&request_id, &attestation, &signature)
+}
+
+/// Both keys verify during the overlap window; the old one retires afterwards.
+#[test]
+fn test_rotate_key_overlap_then_retire() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (old_sk, old_pk) = create_keypair(&env, 1);
+    let (new_sk, new_pk) = create_keypair(&env, 2);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+
+    client.add_provider(&old_pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&old_pk, &tee_hash);
+    client.set_key_overlap(&100);
+
+    let retires_at = rotate(&env, &client, &old_pk, (&old_sk, &old_pk), &new_pk);
+    assert_eq!(retires_at, env.ledger().sequence() + 100);
+    assert_eq!(client.get_active_key(&old_pk), new_pk);
+
+    assert!(client.is_verified(&tee_hash, &old_pk));
+    assert!(client.is_verified(&tee_hash, &new_pk));
+    assert_eq!(submit_request(&env, &client), 1);
+    assert_eq!(
+        attest(&env, &client, 1, (&old_sk, &old_pk), &tee_hash),
+        RequestState::Verified
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = retires_at);
+
+    assert!(!client.is_verified(&tee_hash, &old_pk));
+    assert!(client.is_verified(&tee_hash, &new_pk));
+    assert_eq!(submit_request(&env, &client), 2);
+    assert_eq!(
+        attest(&env, &client, 2, (&old_sk, &old_pk), &tee_hash),
+        RequestState::Rejected(RejectionReason::KeyRetired)
+    );
+    assert_eq!(submit_request(&env, &client), 3);
+    assert_eq!(
+        attest(&env, &client, 3, (&new_sk, &new_pk), &tee_hash),
+        RequestState::Verified
+    );
+}
+
+#[test]
+fn test_rotate_key_emits_event_and_updates_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (old_sk, old_pk) = create_keypair(&env, 1);
+    let (_, new_pk) = create_keypair(&env, 2);
+    let operator = Address::generate(&env);
+
+    apply(
+        &env,
+        &client,
+        &old_sk,
+        &old_pk,
+        &sample_metadata(&env, &operator, &old_pk),
+    );
+    client.approve_provider(&old_pk);
+    rotate(&env, &client, &old_pk, (&old_sk, &old_pk), &new_pk);
+
+    let events_str = std::format!("{:#?}", soroban_sdk::testutils::Events::all(&env.events()));
+    assert!(events_str.contains("KeyRotated"));
+
+    let record = client.get_provider(&old_pk).unwrap();
+    assert_eq!(record.metadata.signing_keys, vec![&env, old_pk.clone(), new_pk.clone()]);
+}
+
+/// Only the active key can rotate, so a replayed rotation is refused.
+#[test]
+fn test_rotate_key_requires_active_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (old_sk, old_pk) = create_keypair(&env, 1);
+    let (_, new_pk) = create_keypair(&env, 2);
+    let (_, other_pk) = create_keypair(&env, 3);
+
+    client.add_provider(&old_pk);
+    rotate(&env, &client, &old_pk, (&old_sk, &old_pk), &new_pk);
+
+    let rotation = KeyRotation {
+        registry: client.address.clone(),
+        provider: old_pk.clone(),
+        current_key: old_pk.clone(),
+        new_key: other_pk,
+    };
+    let signature = sign_xdr(&env, &old_sk, &rotation.clone().to_xdr(&env));
+    assert_eq!(
+        client.try_rotate_key(&rotation, &signature),
+        Err(Ok(VerificationError::KeyNotActive))
+    );
+}
+
+/// A key declared compromised cannot rotate itself to a new key.
+#[test]
+fn test_rotate_key_rejects_compromised_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (old_sk, old_pk) = create_keypair(&env, 1);
+    let (_, attacker_pk) = create_keypair(&env, 2);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+
+    client.add_provider(&old_pk);
+    client.add_tee_hash(&tee_hash);
+    client.declare_compromise(&old_pk, &env.ledger().timestamp());
+
+    let rotation = KeyRotation {
+        registry: client.address.clone(),
+        provider: old_pk.clone(),
+        current_key: old_pk.clone(),
+        new_key: attacker_pk.clone(),
+    };
+    let signature = sign_xdr(&env, &old_sk, &rotation.clone().to_xdr(&env));
+    assert_eq!(
+        client.try_rotate_key(&rotation, &signature),
+        Err(Ok(VerificationError::KeyCompromised))
+    );
+    assert_eq!(client.get_active_key(&old_pk), old_pk);
+    assert!(!client.is_verified(&tee_hash, &attacker_pk));
+}
+
+#[test]
+fn test_rotate_key_rejects_key_in_use() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk_a, pk_a) = create_keypair(&env, 1);
+    let (_, pk_b) = create_keypair(&env, 2);
+
+    client.add_provider(&pk_a);
+    client.add_provider(&pk_b);
+
+    let rotation = KeyRotation {
+        registry: client.address.clone(),
+        provider: pk_a.clone(),
+        current_key: pk_a.clone(),
+        new_key: pk_b,
+    };
+    let signature = sign_xdr(&env, &sk_a, &rotation.clone().to_xdr(&env));
+    assert_eq!(
+        client.try_rotate_key(&rotation, &signature),
+        Err(Ok(VerificationError::KeyInUse))
+    );
+}
+
+/// A rotation signed for another registry deployment cannot be replayed here.
+#[test]
+fn test_rotate_key_rejects_other_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (other, _) = setup(&env);
+    let (old_sk, old_pk) = create_keypair(&env, 1);
+    let (_, new_pk) = create_keypair(&env, 2);
+
+    client.add_provider(&old_pk);
+    other.add_provider(&old_pk);
+
+    let rotation = KeyRotation {
+        registry: other.address.clone(),
+        provider: old_pk.clone(),
+        current_key: old_pk.clone(),
+        new_key: new_pk.clone(),
+    };
+    let signature = sign_xdr(&env, &old_sk, &rotation.clone().to_xdr(&env));
+    other.rotate_key(&rotation, &signature);
+    assert_eq!(
+        client.try_rotate_key(&rotation, &signature),
+        Err(Ok(VerificationError::InvalidSignature))
+    );
+    assert_eq!(client.get_active_key(&old_pk), old_pk);
+}
+
+/// A rotation statement not signed by the current key aborts.
+#[test]
+#[should_panic]
+fn test_rotate_key_invalid_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (_, old_pk) = create_keypair(&env, 1);
+    let (new_sk, new_pk) = create_keypair(&env, 2);
+
+    client.add_provider(&old_pk);
+    rotate(&env, &client, &old_pk, (&new_sk, &old_pk), &new_pk);
+}
+
+// ---------------------------------------------------------------------------
+// Provider suspension
+// ---------------------------------------------------------------------------
+
+/// Suspension rejects the provider temporarily without touching its records.
+#[test]
+fn test_suspend_provider_rejects_until_lapse() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, pk) = create_keypair(&env, 1);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+    let operator = Address::generate(&env);
+
+    apply(
+        &env,
+        &client,
+        &sk,
+        &pk,
+        &sample_metadata(&env, &operator, &pk),
+    );
+    client.approve_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&pk, &tee_hash);
+
+    let until = env.ledger().sequence() + 50;
+    client.suspend_provider(&pk, &until, &String::from_str(&env, "audit"));
+    let events_str = std::format!("{:#?}", soroban_sdk::testutils::Events::all(&env.events()));
+    assert!(events_str.contains("ProviderSuspended"));
+
+    assert!(client.is_suspended(&pk));
+    assert!(!client.is_verified(&tee_hash, &pk));
+    assert_eq!(submit_request(&env, &client), 1);
+    assert_eq!(
+        attest(&env, &client, 1, (&sk, &pk), &tee_hash),
+        RequestState::Rejected(RejectionReason::ProviderSuspended)
+    );
+
+    // Metadata and bindings survive the suspension.
+    assert_eq!(
+        client.get_provider(&pk).unwrap().status,
+        ProviderStatus::Approved
+    );
+    assert!(client.is_provider_tee_allowed(&pk, &tee_hash));
+
+    env.ledger().with_mut(|li| li.sequence_number = until);
+    assert_eq!(client.get_suspension(&pk), None);
+    assert!(client.is_verified(&tee_hash, &pk));
+}
+
+#[test]
+fn test_reinstate_provider_lifts_suspension() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let pk = BytesN::from_array(&env, &[1; 32]);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+
+    client.add_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&pk, &tee_hash);
+
+    let reason = String::from_str(&env, "key review");
+    client.suspend_provider(&pk, &(env.ledger().sequence() + 1_000), &reason);
+    assert_eq!(client.get_suspension(&pk).unwrap().reason, reason);
+
+    client.reinstate_provider(&pk);
+    let events_str = std::format!("{:#?}", soroban_sdk::testutils::Events::all(&env.events()));
+    assert!(events_str.contains("ProviderReinstated"));
+
+    assert!(!client.is_suspended(&pk));
+    assert!(client.is_verified(&tee_hash, &pk));
+    assert_eq!(
+        client.try_reinstate_provider(&pk),
+        Err(Ok(VerificationError::InvalidProviderState))
+    );
+}
+
+#[test]
+fn test_suspend_provider_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let pk = BytesN::from_array(&env, &[1; 32]);
+    let reason = String::from_str(&env, "audit");
+    let until = env.ledger().sequence() + 10;
+
+    assert_eq!(
+        client.try_suspend_provider(&pk, &until, &reason),
+        Err(Ok(VerificationError::ProviderNotFound))
+    );
+
+    client.add_provider(&pk);
+    assert_eq!(
+        client.try_suspend_provider(&pk, &env.ledger().sequence(), &reason),
+        Err(Ok(VerificationError::InvalidSuspension))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Key compromise
+// ---------------------------------------------------------------------------
+
+// Provenance mock recording minted and flagged certificates. Like the real
+// contract, it refuses a second certificate for a manifest hash until the
+// first one is revoked.
+#[soroban_sdk::contract]
+pub struct ProvenanceMock;
+
+#[soroban_sdk::contractimpl]
+impl ProvenanceMock {
+    pub fn mint(
+        env: Env,
+        _minter: Address,
+        to: Address,
+        details: provenance::CertificateDetails,
+    ) -> u64 {
+        let manifest_key = (2u32, details.manifest_hash.clone());
+        if env.storage().instance().has(&manifest_key) {
+            panic!("Certificate already exists for this manifest hash");
+        }
+        let next: u64 = env.storage().instance().get(&0u32).unwrap_or(0) + 1;
+        env.storage().instance().set(&0u32, &next);
+        env.storage().instance().set(&manifest_key, &next);
+        env.storage().instance().set(&(1u32, next), &(to, details));
+        next
+    }
+
+    pub fn revoke_certificate(env: Env, _minter: Address, certificate_id: u64, reason: String) {
+        let (_, details): (Address, provenance::CertificateDetails) =
+            env.storage().instance().get(&(1u32, certificate_id)).unwrap();
+        env.storage().instance().remove(&(2u32, details.manifest_hash));
+        env.storage().instance().set(&certificate_id, &reason);
+    }
+
+    pub fn get_mint(env: Env, certificate_id: u64) -> Option<(Address, provenance::CertificateDetails)> {
+        env.storage().instance().get(&(1u32, certificate_id))
+    }
+
+    pub fn flag_certificate(env: Env, _minter: Address, certificate_id: u64, reason: String) {
+        env.storage().instance().set(&certificate_id, &reason);
+    }
+
+    pub fn get_flag(env: Env, certificate_id: u64) -> Option<String> {
+        env.storage().instance().get(&certificate_id)
+    }
+}
+
+/// Requests verified inside the compromise window are suspect; earlier ones are not.
+#[test]
+fn test_declare_compromise_flags_later_requests() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, pk) = create_keypair(&env, 1);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+
+    client.add_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&pk, &tee_hash);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(submit_request(&env, &client), 1);
+    attest(&env, &client, 1, (&sk, &pk), &tee_hash);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(submit_request(&env, &client), 2);
+    attest(&env, &client, 2, (&sk, &pk), &tee_hash);
+    assert_eq!(client.get_request(&2).unwrap().verified_by, Some(pk.clone()));
+
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+    client.declare_compromise(&pk, &1_500);
+    let events_str = std::format!("{:#?}", soroban_sdk::testutils::Events::all(&env.events()));
+    assert!(events_str.contains("KeyCompromised"));
+
+    assert!(!client.is_request_suspect(&1));
+    assert!(client.is_request_suspect(&2));
+    assert_eq!(client.get_compromise(&pk).unwrap().since, 1_500);
+
+    // The compromised key can no longer attest.
+    assert!(!client.is_verified(&tee_hash, &pk));
+    assert_eq!(submit_request(&env, &client), 3);
+    assert_eq!(
+        attest(&env, &client, 3, (&sk, &pk), &tee_hash),
+        RequestState::Rejected(RejectionReason::KeyCompromised)
+    );
+    assert!(!client.is_request_suspect(&3));
+}
+
+#[test]
+fn test_declare_compromise_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let pk = BytesN::from_array(&env, &[1; 32]);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    assert_eq!(
+        client.try_declare_compromise(&pk, &500),
+        Err(Ok(VerificationError::ProviderNotFound))
+    );
+
+    client.add_provider(&pk);
+    assert_eq!(
+        client.try_declare_compromise(&pk, &1_001),
+        Err(Ok(VerificationError::InvalidCompromise))
+    );
+
+    // Re-declaring never shrinks the window.
+    client.declare_compromise(&pk, &500);
+    client.declare_compromise(&pk, &800);
+    assert_eq!(client.get_compromise(&pk).unwrap().since, 500);
+}
+
+#[test]
+fn test_flag_suspect_certificate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Registry, ());
+    let client = RegistryClient::new(&env, &contract_id);
+    let provenance_id = env.register(ProvenanceMock, ());
+    let provenance = ProvenanceMockClient::new(&env, &provenance_id);
+    client.init(&Address::generate(&env), &provenance_id);
+
+    let (sk, pk) = create_keypair(&env, 1);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+    client.add_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&pk, &tee_hash);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(submit_request(&env, &client), 1);
+    attest(&env, &client, 1, (&sk, &pk), &tee_hash);
+
+    assert_eq!(
+        client.try_flag_suspect_certificate(&1),
+        Err(Ok(VerificationError::NotSuspect))
+    );
+
+    client.declare_compromise(&pk, &1_000);
+    let certificate_id = client.get_request(&1).unwrap().certificate_id.unwrap();
+    client.flag_suspect_certificate(&1);
+    assert_eq!(
+        provenance.get_flag(&certificate_id),
+        Some(String::from_str(&env, "KeyCompromised"))
+    );
+    assert_eq!(provenance.get_flag(&(certificate_id + 1)), None);
+}
+
+/// Rejection codes are part of the public interface and must stay stable.
+#[test]
+fn test_rejection_reason_codes_are_stable() {
+    assert_eq!(RejectionReason::UnauthorizedProvider as u32, 1);
+    assert_eq!(RejectionReason::UntrustedTee as u32, 2);
+    assert_eq!(RejectionReason::AttestationMismatch as u32, 3);
+    assert_eq!(RejectionReason::HashMismatch as u32, 4);
+    assert_eq!(RejectionReason::Expired as u32, 5);
+    assert_eq!(RejectionReason::ProviderSuspended as u32, 6);
+    assert_eq!(RejectionReason::ProviderTeeNotAllowed as u32, 7);
+    assert_eq!(RejectionReason::KeyRetired as u32, 8);
+    assert_eq!(RejectionReason::KeyCompromised as u32, 9);
+    assert_eq!(RejectionReason::UnsupportedAlgorithm as u32, 10);
+    assert_eq!(RejectionReason::ClaimConflict as u32, 11);
+    assert_eq!(RejectionReason::ChunkProofInvalid as u32, 12);
+}
+
+// ---------------------------------------------------------------------------
+// verify_and_mint content hashing
+// ---------------------------------------------------------------------------
+
+/// Registers the registry against a `ProvenanceMock` so minting succeeds.
+fn setup_with_provenance(env: &Env) -> RegistryClient<'_> {
+    let contract_id = env.register(Registry, ());
+    let client = RegistryClient::new(env, &contract_id);
+    let provenance_id = env.register(ProvenanceMock, ());
+    client.init(&Address::generate(env), &provenance_id);
+    client
+}
+
+fn sha256_hex(env: &Env, content: &str) -> std::string::String {
+    let digest = env
+        .crypto()
+        .sha256(&soroban_sdk::Bytes::from_slice(env, content.as_bytes()))
+        .to_array();
+    digest.iter().map(|b| std::format!("{b:02x}")).collect()
+}
+
+#[test]
+fn test_verify_and_mint_full_digest() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let hex = sha256_hex(&env, "hello stellarproof");
+
+    let result = client.verify_and_mint(&content, &String::from_str(&env, &hex), &owner, &owner);
+    assert!(result.success);
+    assert_eq!(result.state, RequestState::Verified);
+    assert_eq!(result.certificate_id, Some(1));
+    assert_eq!(result.content_hash, String::from_str(&env, &hex));
+    assert_eq!(result.content_hash.len(), 64);
+    assert_eq!(result.hash_algorithm, HashAlgorithm::Sha256);
+}
+
+#[test]
+fn test_verify_and_mint_accepts_prefixed_and_multihash_forms() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let hex = sha256_hex(&env, "hello stellarproof");
+
+    for expected in [
+        std::format!("0x{hex}"),
+        std::format!("1220{hex}"),
+        hex.to_uppercase(),
+    ] {
+        let result = client.verify_and_mint(&content, &String::from_str(&env, &expected), &owner, &owner);
+        assert!(result.success, "{expected} should match");
+        assert_eq!(result.content_hash, String::from_str(&env, &hex));
+    }
+}
+
+#[test]
+fn test_verify_and_mint_digest() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let digest: BytesN<32> = env.crypto().sha256(&content.to_bytes()).into();
+
+    let result = client.verify_and_mint_digest(&content, &digest, &HashAlgorithm::Sha256, &owner, &owner);
+    assert!(result.success);
+
+    let wrong = BytesN::from_array(&env, &[0; 32]);
+    let result = client.verify_and_mint_digest(&content, &wrong, &HashAlgorithm::Sha256, &owner, &owner);
+    assert!(!result.success);
+    assert_eq!(result.state, RequestState::Rejected(RejectionReason::HashMismatch));
+    assert_eq!(result.certificate_id, None);
+}
+
+/// Truncated hashes only verify in legacy mode, and keep their truncated form.
+#[test]
+fn test_verify_and_mint_legacy_truncated_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let truncated = String::from_str(&env, &sha256_hex(&env, "hello stellarproof")[..16]);
+
+    assert_eq!(
+        client.try_verify_and_mint(&content, &truncated, &owner, &owner),
+        Err(Ok(VerificationError::InvalidHash))
+    );
+
+    client.set_legacy_hash_mode(&true);
+    assert!(client.get_legacy_hash_mode());
+    let result = client.verify_and_mint(&content, &truncated, &owner, &owner);
+    assert!(result.success);
+    assert_eq!(result.content_hash, truncated);
+}
+
+#[test]
+fn test_verify_and_mint_rejects_malformed_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let hex = sha256_hex(&env, "hello stellarproof");
+
+    for expected in [std::format!("{}zz", &hex[..62]), hex[..40].into(), "".into()] {
+        assert_eq!(
+            client.try_verify_and_mint(&content, &String::from_str(&env, &expected), &owner, &owner),
+            Err(Ok(VerificationError::InvalidHash))
+        );
+    }
+}
+
+#[test]
+fn test_verify_and_mint_keccak256_multihash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let digest = env.crypto().keccak256(&content.to_bytes()).to_array();
+    let hex: std::string::String = digest.iter().map(|b| std::format!("{b:02x}")).collect();
+
+    let result =
+        client.verify_and_mint(&content, &String::from_str(&env, &std::format!("1b20{hex}")), &owner, &owner);
+    assert!(result.success);
+    assert_eq!(result.hash_algorithm, HashAlgorithm::Keccak256);
+    assert_eq!(result.content_hash, String::from_str(&env, &hex));
+
+    // Without the multihash tag the digest is taken to be SHA-256.
+    let result = client.verify_and_mint(&content, &String::from_str(&env, &hex), &owner, &owner);
+    assert!(!result.success);
+    assert_eq!(result.hash_algorithm, HashAlgorithm::Sha256);
+}
+
+/// BLAKE digests are recorded by the pipeline but cannot be recomputed on-chain.
+#[test]
+fn test_verify_and_mint_unsupported_algorithm() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let digest = BytesN::from_array(&env, &[0x11; 32]);
+
+    let result = client.verify_and_mint_digest(&content, &digest, &HashAlgorithm::Blake3, &owner, &owner);
+    assert!(!result.success);
+    assert_eq!(
+        result.state,
+        RequestState::Rejected(RejectionReason::UnsupportedAlgorithm)
+    );
+
+    let expected = std::format!("a0e40220{}", "11".repeat(32));
+    let result = client.verify_and_mint(&content, &String::from_str(&env, &expected), &owner, &owner);
+    assert_eq!(result.hash_algorithm, HashAlgorithm::Blake2b256);
+    assert_eq!(
+        result.state,
+        RequestState::Rejected(RejectionReason::UnsupportedAlgorithm)
+    );
+}
+
+// ---------------------------------------------------------------------------
+// verify_and_mint authorization and claim priority
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_verify_and_mint_requires_owner_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let hex = String::from_str(&env, &sha256_hex(&env, "hello stellarproof"));
+
+    client.verify_and_mint(&content, &hex, &owner, &owner);
+    assert_eq!(env.auths()[0].0, owner);
+}
+
+#[test]
+fn test_verify_and_mint_by_delegate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let hex = String::from_str(&env, &sha256_hex(&env, "hello stellarproof"));
+
+    assert_eq!(
+        client.try_verify_and_mint(&content, &hex, &owner, &delegate),
+        Err(Ok(VerificationError::Unauthorized))
+    );
+
+    client.add_delegate(&owner, &delegate);
+    assert!(client.is_delegate(&owner, &delegate));
+    let result = client.verify_and_mint(&content, &hex, &owner, &delegate);
+    assert!(result.success);
+    assert_eq!(client.get_mint(&hex).unwrap().owner, owner);
+
+    client.remove_delegate(&owner, &delegate);
+    assert!(!client.is_delegate(&owner, &delegate));
+}
+
+/// Without the owner's (or a delegate's) signature the mint aborts.
+#[test]
+#[should_panic]
+fn test_verify_and_mint_without_auth_panics() {
+    let env = Env::default();
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let hex = String::from_str(&env, &sha256_hex(&env, "hello stellarproof"));
+
+    client.verify_and_mint(&content, &hex, &owner, &owner);
+}
+
+fn claim_commitment(env: &Env, owner: &Address, manifest_hash: &String) -> BytesN<32> {
+    let preimage = ClaimPreimage {
+        owner: owner.clone(),
+        manifest_hash: manifest_hash.clone(),
+    };
+    env.crypto().sha256(&preimage.to_xdr(env)).into()
+}
+
+/// A creator who committed before a squatter minted can contest the mint,
+/// after which only the creator can mint the content.
+#[test]
+fn test_contest_squatted_mint() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Registry, ());
+    let client = RegistryClient::new(&env, &contract_id);
+    let provenance_id = env.register(ProvenanceMock, ());
+    let provenance = ProvenanceMockClient::new(&env, &provenance_id);
+    client.init(&Address::generate(&env), &provenance_id);
+
+    let creator = Address::generate(&env);
+    let squatter = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let hex = sha256_hex(&env, "hello stellarproof");
+    let manifest_hash = String::from_str(&env, &hex);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let commitment = claim_commitment(&env, &creator, &manifest_hash);
+    client.register_claim(&creator, &commitment);
+    assert_eq!(client.get_claim_commitment(&commitment), Some(1_000));
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    let squatted = client.verify_and_mint(&content, &manifest_hash, &squatter, &squatter);
+    let cert_id = squatted.certificate_id.unwrap();
+
+    assert_eq!(
+        client.try_contest_mint(&squatter, &manifest_hash),
+        Err(Ok(VerificationError::NoClaim))
+    );
+    let prefixed = String::from_str(&env, &std::format!("0x{}", hex.to_uppercase()));
+    assert_eq!(client.contest_mint(&creator, &prefixed), cert_id);
+    assert_eq!(
+        provenance.get_flag(&cert_id),
+        Some(String::from_str(&env, "ClaimContested"))
+    );
+    assert_eq!(client.get_claim(&manifest_hash).unwrap().owner, creator);
+
+    let result = client.verify_and_mint(&content, &manifest_hash, &squatter, &squatter);
+    assert!(!result.success);
+    assert_eq!(result.state, RequestState::Rejected(RejectionReason::ClaimConflict));
+    let result = client.verify_and_mint(&content, &manifest_hash, &creator, &creator);
+    assert!(result.success);
+    assert_eq!(result.state, RequestState::Verified);
+    let creator_cert = result.certificate_id.unwrap();
+    assert_ne!(creator_cert, cert_id);
+    assert_eq!(provenance.get_mint(&creator_cert).unwrap().0, creator);
+    assert_eq!(client.get_mint(&manifest_hash).unwrap().owner, creator);
+}
+
+/// A commitment made after the mint gives no priority.
+#[test]
+fn test_contest_mint_requires_earlier_commitment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let creator = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let content = String::from_str(&env, "hello stellarproof");
+    let manifest_hash = String::from_str(&env, &sha256_hex(&env, "hello stellarproof"));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.verify_and_mint(&content, &manifest_hash, &minter, &minter);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.register_claim(&creator, &claim_commitment(&env, &creator, &manifest_hash));
+    assert_eq!(
+        client.try_contest_mint(&creator, &manifest_hash),
+        Err(Ok(VerificationError::NotContestable))
+    );
+    assert_eq!(client.get_claim(&manifest_hash), None);
+}
+
+#[test]
+fn test_contest_mint_rejects_truncated_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let creator = Address::generate(&env);
+    let truncated = String::from_str(&env, &sha256_hex(&env, "hello stellarproof")[..16]);
+
+    assert_eq!(
+        client.try_contest_mint(&creator, &truncated),
+        Err(Ok(VerificationError::InvalidHash))
+    );
+}
+// ---------------------------------------------------------------------------
+// Chunked Merkle-root verification
+// ---------------------------------------------------------------------------
+
+fn merkle_leaf(env: &Env, chunk: &[u8]) -> [u8; 32] {
+    let mut data = std::vec![0x00];
+    data.extend_from_slice(chunk);
+    env.crypto()
+        .sha256(&soroban_sdk::Bytes::from_slice(env, &data))
+        .to_array()
+}
+
+fn merkle_node(env: &Env, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut data = std::vec![0x01];
+    data.extend_from_slice(left);
+    data.extend_from_slice(right);
+    env.crypto()
+        .sha256(&soroban_sdk::Bytes::from_slice(env, &data))
+        .to_array()
+}
+
+/// Builds every level of the chunk tree, leaves first, promoting odd nodes.
+fn merkle_levels(
+    env: &Env,
+    chunks: &[std::vec::Vec<u8>],
+) -> std::vec::Vec<std::vec::Vec<[u8; 32]>> {
+    let mut levels = std::vec![chunks
+        .iter()
+        .map(|c| merkle_leaf(env, c))
+        .collect::<std::vec::Vec<_>>()];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => merkle_node(env, left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+fn chunk_proof(
+    env: &Env,
+    chunks: &[std::vec::Vec<u8>],
+    levels: &[std::vec::Vec<[u8; 32]>],
+    index: u32,
+) -> ChunkProof {
+    let mut siblings = soroban_sdk::Vec::new(env);
+    let mut i = index as usize;
+    for level in &levels[..levels.len() - 1] {
+        let sibling = if i % 2 == 1 {
+            Some(level[i - 1])
+        } else {
+            level.get(i + 1).copied()
+        };
+        if let Some(sibling) = sibling {
+            siblings.push_back(BytesN::from_array(env, &sibling));
+        }
+        i /= 2;
+    }
+    ChunkProof {
+        index,
+        chunk: soroban_sdk::Bytes::from_slice(env, &chunks[index as usize]),
+        siblings,
+    }
+}
+
+/// Ten 4-byte chunks, the last one shorter.
+fn sample_chunks() -> std::vec::Vec<std::vec::Vec<u8>> {
+    (0..10u8)
+        .map(|i| {
+            if i == 9 {
+                std::vec![i; 2]
+            } else {
+                std::vec![i; 4]
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_chunked_verification_mints_root_certificate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let chunks = sample_chunks();
+    let levels = merkle_levels(&env, &chunks);
+    let root = BytesN::from_array(&env, &levels.last().unwrap()[0]);
+    let commitment = MerkleCommitment {
+        root: root.clone(),
+        chunk_size: 4,
+        chunk_count: 10,
+    };
+
+    let indices = client.begin_chunked_verification(&commitment, &owner, &owner);
+    assert_eq!(indices.len(), 4);
+    for (i, index) in indices.iter().enumerate() {
+        assert!(index < 10);
+        assert!(!indices.slice(i as u32 + 1..).contains(index));
+    }
+    assert_eq!(
+        client.get_chunk_challenge(&root, &owner).unwrap().indices,
+        indices
+    );
+
+    let mut proofs = soroban_sdk::Vec::new(&env);
+    for index in indices.iter() {
+        proofs.push_back(chunk_proof(&env, &chunks, &levels, index));
+    }
+    let result = client.complete_chunked_verification(&root, &owner, &proofs);
+    assert!(result.success);
+    assert_eq!(result.state, RequestState::Verified);
+    assert_eq!(result.certificate_id, Some(1));
+    let root_hex: std::string::String = root
+        .to_array()
+        .iter()
+        .map(|b| std::format!("{b:02x}"))
+        .collect();
+    assert_eq!(result.content_hash, String::from_str(&env, &root_hex));
+    assert_eq!(client.get_chunk_challenge(&root, &owner), None);
+}
+
+/// Every chunk index verifies, including promoted and short trailing chunks.
+#[test]
+fn test_chunk_proofs_for_every_index() {
+    let env = Env::default();
+    let contract_id = env.register(Registry, ());
+    let chunks = sample_chunks();
+    let levels = merkle_levels(&env, &chunks);
+    let commitment = MerkleCommitment {
+        root: BytesN::from_array(&env, &levels.last().unwrap()[0]),
+        chunk_size: 4,
+        chunk_count: 10,
+    };
+
+    env.as_contract(&contract_id, || {
+        for index in 0..10 {
+            let proof = chunk_proof(&env, &chunks, &levels, index);
+            assert!(
+                Registry::verify_chunk(&env, &commitment, &proof),
+                "chunk {index}"
+            );
+        }
+        let mut short = chunk_proof(&env, &chunks, &levels, 3);
+        short.chunk = soroban_sdk::Bytes::from_slice(&env, &[3; 2]);
+        assert!(!Registry::verify_chunk(&env, &commitment, &short));
+    });
+}
+
+#[test]
+fn test_chunked_verification_rejects_tampered_chunk() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let chunks = sample_chunks();
+    let levels = merkle_levels(&env, &chunks);
+    let root = BytesN::from_array(&env, &levels.last().unwrap()[0]);
+    let commitment = MerkleCommitment {
+        root: root.clone(),
+        chunk_size: 4,
+        chunk_count: 10,
+    };
+
+    let indices = client.begin_chunked_verification(&commitment, &owner, &owner);
+    let mut proofs = soroban_sdk::Vec::new(&env);
+    for index in indices.iter() {
+        proofs.push_back(chunk_proof(&env, &chunks, &levels, index));
+    }
+    let mut tampered = proofs.get(0).unwrap();
+    tampered.chunk = soroban_sdk::Bytes::from_slice(&env, &[0xEE; 4]);
+    if tampered.index == 9 {
+        tampered.chunk = soroban_sdk::Bytes::from_slice(&env, &[0xEE; 2]);
+    }
+    proofs.set(0, tampered);
+
+    let result = client.complete_chunked_verification(&root, &owner, &proofs);
+    assert!(!result.success);
+    assert_eq!(result.certificate_id, None);
+    assert_eq!(
+        result.state,
+        RequestState::Rejected(RejectionReason::ChunkProofInvalid)
+    );
+    // The challenge is consumed, so a second attempt needs a fresh one.
+    assert_eq!(
+        client.try_complete_chunked_verification(&root, &owner, &proofs),
+        Err(Ok(VerificationError::NotFound))
+    );
+}
+
+#[test]
+fn test_chunked_verification_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let root = BytesN::from_array(&env, &[9; 32]);
+
+    for (chunk_size, chunk_count) in [(0, 4), (MAX_CHUNK_SIZE + 1, 4), (1024, 0)] {
+        let commitment = MerkleCommitment {
+            root: root.clone(),
+            chunk_size,
+            chunk_count,
+        };
+        assert_eq!(
+            client.try_begin_chunked_verification(&commitment, &owner, &owner),
+            Err(Ok(VerificationError::InvalidChunkParams))
+        );
+    }
+
+    // Content smaller than the sample count is sampled chunk by chunk.
+    let commitment = MerkleCommitment {
+        root: root.clone(),
+        chunk_size: 1024,
+        chunk_count: 2,
+    };
+    let indices = client.begin_chunked_verification(&commitment, &owner, &owner);
+    assert_eq!(indices.len(), 2);
+    assert!(indices.contains(0) && indices.contains(1));
+    assert_eq!(
+        client.try_begin_chunked_verification(&commitment, &owner, &owner),
+        Err(Ok(VerificationError::ChallengePending))
+    );
+
+    // Another account's challenge on the same root does not block this one.
+    let other = Address::generate(&env);
+    client.begin_chunked_verification(&commitment, &other, &other);
+    assert_eq!(
+        client.get_chunk_challenge(&root, &other).unwrap().owner,
+        other
+    );
+    assert_eq!(
+        client.get_chunk_challenge(&root, &owner).unwrap().indices,
+        indices
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Binary content
+// ---------------------------------------------------------------------------
+
+/// Binary content is hashed as raw bytes, matching a hash of the original file.
+#[test]
+fn test_verify_and_mint_bytes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    // PNG signature followed by bytes that are not valid UTF-8.
+    let content = soroban_sdk::Bytes::from_slice(
+        &env,
+        &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0xfe],
+    );
+    let digest = env.crypto().sha256(&content).to_array();
+    let hex: std::string::String = digest.iter().map(|b| std::format!("{b:02x}")).collect();
+    let expected = String::from_str(&env, &hex);
+
+    let result = client.verify_and_mint_bytes(&content, &expected, &owner, &owner);
+    assert!(result.success);
+    assert_eq!(result.content_hash, expected);
+    assert_eq!(result.certificate_id, Some(1));
+
+    let other = soroban_sdk::Bytes::from_slice(&env, &[0x89, b'P', b'N', b'G']);
+    let result = client.verify_and_mint_bytes(&other, &expected, &owner, &owner);
+    assert!(!result.success);
+    assert_eq!(
+        result.state,
+        RequestState::Rejected(RejectionReason::HashMismatch)
+    );
+}
+
+#[test]
+fn test_verify_and_mint_bytes_size_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = setup_with_provenance(&env);
+    let owner = Address::generate(&env);
+    let expected = String::from_str(&env, &sha256_hex(&env, ""));
+
+    assert_eq!(
+        client.try_verify_and_mint_bytes(&soroban_sdk::Bytes::new(&env), &expected, &owner, &owner),
+        Err(Ok(VerificationError::EmptyContent))
+    );
+
+    let mut content = soroban_sdk::Bytes::new(&env);
+    content.extend_from_array(&[0u8; 1024]);
+    let mut too_large = soroban_sdk::Bytes::new(&env);
+    for _ in 0..64 {
+        too_large.append(&content);
+    }
+    assert!(client
+        .try_verify_and_mint_bytes(&too_large, &expected, &owner, &owner)
+        .is_ok());
+    too_large.push_back(0);
+    assert_eq!(
+        client.try_verify_and_mint_bytes(&too_large, &expected, &owner, &owner),
+        Err(Ok(VerificationError::ContentTooLarge))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Request submission
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_submit_request_allocates_ids() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let requester = Address::generate(&env);
+    let content_hash = BytesN::from_array(&env, &[5; 32]);
+    env.ledger().with_mut(|li| li.sequence_number = 42);
+
+    let storage_id = String::from_str(&env, "ipfs://content");
+    let id = client.submit_request(
+        &requester,
+        &content_hash,
+        &HashAlgorithm::Keccak256,
+        &storage_id,
+    );
+    assert_eq!(id, 1);
+    assert_eq!(env.auths()[0].0, requester.clone());
+    assert_eq!(
+        client.get_request(&1),
+        Some(VerificationRequest {
+            id: 1,
+            requester: requester.clone(),
+            content_hash: content_hash.clone(),
+            hash_algorithm: HashAlgorithm::Keccak256,
+            storage_id: storage_id.clone(),
+            created_at: 42,
+            deadline: 42 + 17_280,
+            state: RequestState::Pending,
+            verified_by: None,
+            verified_at: None,
+            certificate_id: None,
+        })
+    );
+    assert_eq!(
+        client.submit_request(&requester, &content_hash, &HashAlgorithm::Sha256, &storage_id),
+        2
+    );
+    assert_eq!(client.get_request(&3), None);
+}
+
+#[test]
+fn test_submit_request_never_overwrites() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    assert_eq!(submit_request(&env, &client), 1);
+    let content_hash = client.get_request(&1).unwrap().content_hash;
+
+    // Even with a corrupted counter an existing request is never replaced.
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&DataKey::NextRequestId, &1u64);
+    });
+    let requester = Address::generate(&env);
+    assert_eq!(
+        client.try_submit_request(
+            &requester,
+            &BytesN::from_array(&env, &[2; 32]),
+            &HashAlgorithm::Sha256,
+            &String::from_str(&env, "ipfs://other")
+        ),
+        Err(Ok(VerificationError::RequestExists))
+    );
+    assert_eq!(client.get_request(&1).unwrap().content_hash, content_hash);
+}
+
+#[test]
+fn test_process_verification_requires_submitted_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, pk) = create_keypair(&env, 1);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+    client.add_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+
+    let attestation = Attestation {
+        provider: pk.clone(),
+        tee_hash: tee_hash.clone(),
+        request_id: 1,
+    };
+    let signature = sign_xdr(&env, &sk, &attestation.clone().to_xdr(&env));
+    assert_eq!(
+        client.try_process_verification(&1, &attestation, &signature),
+        Err(Ok(VerificationError::NotFound))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Minting from process_verification
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_process_verification_mints_certificate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Registry, ());
+    let client = RegistryClient::new(&env, &contract_id);
+    let provenance_id = env.register(ProvenanceMock, ());
+    let provenance = ProvenanceMockClient::new(&env, &provenance_id);
+    client.init(&Address::generate(&env), &provenance_id);
+
+    let (sk, pk) = create_keypair(&env, 1);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+    client.add_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&pk, &tee_hash);
+
+    let requester = Address::generate(&env);
+    let content_hash = BytesN::from_array(&env, &[0xab; 32]);
+    let storage_id = String::from_str(&env, "ipfs://photo");
+    let id = client.submit_request(
+        &requester,
+        &content_hash,
+        &HashAlgorithm::Sha256,
+        &storage_id,
+    );
+
+    let attestation = Attestation {
+        provider: pk.clone(),
+        tee_hash: tee_hash.clone(),
+        request_id: id,
+    };
+    let payload = attestation.clone().to_xdr(&env);
+    let signature = sign_xdr(&env, &sk, &payload);
+    assert_eq!(
+        client.process_verification(&id, &attestation, &signature),
+        RequestState::Verified
+    );
+
+    let req = client.get_request(&id).unwrap();
+    assert_eq!(req.certificate_id, Some(1));
+    let (owner, details) = provenance.get_mint(&1).unwrap();
+    assert_eq!(owner, requester);
+    assert_eq!(details.storage_id, storage_id);
+    assert_eq!(
+        details.manifest_hash,
+        String::from_str(&env, &"ab".repeat(32))
+    );
+    let attestation_hash: std::string::String = env
+        .crypto()
+        .sha256(&payload)
+        .to_array()
+        .iter()
+        .map(|b| std::format!("{b:02x}"))
+        .collect();
+    assert_eq!(
+        details.attestation_hash,
+        String::from_str(&env, &attestation_hash)
+    );
+    assert_eq!(details.job, provenance::JobResult::ContentIntegrity);
+    assert_eq!(
+        client
+            .get_mint(&details.manifest_hash)
+            .unwrap()
+            .certificate_id,
+        1
+    );
+}
+
+/// A verified request whose mint fails is recorded as `Failed`, not `Verified`.
+#[test]
+fn test_process_verification_records_mint_failure() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Registry, ());
+    let client = RegistryClient::new(&env, &contract_id);
+    // Not a contract, so every mint fails.
+    client.init(&Address::generate(&env), &Address::generate(&env));
+
+    let (sk, pk) = create_keypair(&env, 1);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+    client.add_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&pk, &tee_hash);
+
+    assert_eq!(submit_request(&env, &client), 1);
+    assert_eq!(
+        attest(&env, &client, 1, (&sk, &pk), &tee_hash),
+        RequestState::Failed
+    );
+    let req = client.get_request(&1).unwrap();
+    assert_eq!(req.certificate_id, None);
+    assert_eq!(req.verified_by, Some(pk.clone()));
+    assert_eq!(
+        client.try_process_verification(
+            &1,
+            &Attestation {
+                provider: pk.clone(),
+                tee_hash: tee_hash.clone(),
+                request_id: 1,
+            },
+            &BytesN::from_array(&env, &[0; 64])
+        ),
+        Err(Ok(VerificationError::AlreadyProcessed))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Request expiry
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_process_verification_expires_late_requests() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, pk) = create_keypair(&env, 1);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+    client.add_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&pk, &tee_hash);
+    client.set_request_ttl(&10);
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    assert_eq!(submit_request(&env, &client), 1);
+    assert_eq!(client.get_request(&1).unwrap().deadline, 110);
+
+    env.ledger().with_mut(|li| li.sequence_number = 111);
+    assert_eq!(client.get_request(&1).unwrap().state, RequestState::Expired);
+    assert_eq!(
+        attest(&env, &client, 1, (&sk, &pk), &tee_hash),
+        RequestState::Expired
+    );
+    let req = client.get_request(&1).unwrap();
+    assert_eq!(req.verified_by, None);
+    assert_eq!(req.certificate_id, None);
+    assert_eq!(
+        client.try_process_verification(
+            &1,
+            &Attestation {
+                provider: pk.clone(),
+                tee_hash: tee_hash.clone(),
+                request_id: 1,
+            },
+            &BytesN::from_array(&env, &[0; 64])
+        ),
+        Err(Ok(VerificationError::AlreadyProcessed))
+    );
+
+    // Requests still within their deadline are processed normally.
+    assert_eq!(submit_request(&env, &client), 2);
+    env.ledger().with_mut(|li| li.sequence_number = 121);
+    assert_eq!(
+        attest(&env, &client, 2, (&sk, &pk), &tee_hash),
+        RequestState::Verified
+    );
+    assert_eq!(client.get_request(&3), None);
+}
+
+#[test]
+fn test_set_request_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    assert_eq!(client.get_request_ttl(), 17_280);
+    assert_eq!(
+        client.try_set_request_ttl(&0),
+        Err(Ok(VerificationError::InvalidTtl))
+    );
+    assert_eq!(
+        client.try_set_request_ttl(&u32::MAX),
+        Err(Ok(VerificationError::InvalidTtl))
+    );
+    client.set_request_ttl(&500);
+    assert_eq!(client.get_request_ttl(), 500);
+}
+
+// ---------------------------------------------------------------------------
+// Cancellation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_cancel_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, pk) = create_keypair(&env, 1);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+    client.add_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&pk, &tee_hash);
+
+    let requester = Address::generate(&env);
+    let id = client.submit_request(
+        &requester,
+        &BytesN::from_array(&env, &[1; 32]),
+        &HashAlgorithm::Sha256,
+        &String::from_str(&env, "ipfs://content"),
+    );
+    client.cancel_request(&id);
+    assert_eq!(env.auths()[0].0, requester);
+    assert_eq!(
+        client.get_request(&id).unwrap().state,
+        RequestState::Cancelled
+    );
+
+    // Attestations for a cancelled request are refused.
+    let attestation = Attestation {
+        provider: pk.clone(),
+        tee_hash: tee_hash.clone(),
+        request_id: id,
+    };
+    let signature = sign_xdr(&env, &sk, &attestation.clone().to_xdr(&env));
+    assert_eq!(
+        client.try_process_verification(&id, &attestation, &signature),
+        Err(Ok(VerificationError::AlreadyProcessed))
+    );
+    assert_eq!(
+        client.try_cancel_request(&id),
+        Err(Ok(VerificationError::AlreadyProcessed))
+    );
+    assert_eq!(
+        client.try_cancel_request(&99),
+        Err(Ok(VerificationError::NotFound))
+    );
+}
+
+/// Processed and expired requests can no longer be cancelled.
+#[test]
+fn test_cancel_request_only_while_pending() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, pk) = create_keypair(&env, 1);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+    client.add_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&pk, &tee_hash);
+    client.set_request_ttl(&10);
+
+    assert_eq!(submit_request(&env, &client), 1);
+    attest(&env, &client, 1, (&sk, &pk), &tee_hash);
+    assert_eq!(
+        client.try_cancel_request(&1),
+        Err(Ok(VerificationError::AlreadyProcessed))
+    );
+
+    assert_eq!(submit_request(&env, &client), 2);
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    assert_eq!(
+        client.try_cancel_request(&2),
+        Err(Ok(VerificationError::AlreadyProcessed))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_cancel_request_requires_requester_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    assert_eq!(submit_request(&env, &client), 1);
+
+    env.set_auths(&[]);
+    client.cancel_request(&1);
+}
+
+// ---------------------------------------------------------------------------
+// Request lifecycle events
+// ---------------------------------------------------------------------------
+
+fn last_events(env: &Env) -> std::string::String {
+    std::format!("{:#?}", soroban_sdk::testutils::Events::all(&env.events()))
+}
+
+#[test]
+fn test_request_lifecycle_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let (sk, pk) = create_keypair(&env, 1);
+    let tee_hash = BytesN::from_array(&env, &[77; 32]);
+    client.add_provider(&pk);
+    client.add_tee_hash(&tee_hash);
+    client.allow_provider_tee(&pk, &tee_hash);
+    client.set_request_ttl(&10);
+
+    assert_eq!(submit_request(&env, &client), 1);
+    let events = last_events(&env);
+    assert!(events.contains("request_submitted"), "{events}");
+    assert!(events.contains("requester"), "{events}");
+
+    attest(&env, &client, 1, (&sk, &pk), &tee_hash);
+    let events = last_events(&env);
+    assert!(events.contains("request_verified"), "{events}");
+    assert!(events.contains("certificate_id"), "{events}");
+
+    assert_eq!(submit_request(&env, &client), 2);
+    attest(&env, &client, 2, (&sk, &pk), &BytesN::from_array(&env, &[78; 32]));
+    let events = last_events(&env);
+    assert!(events.contains("request_rejected"), "{events}");
+    // RejectionReason::UntrustedTee
+    assert!(events.contains("val: U32(2)"), "{events}");
+
+    assert_eq!(submit_request(&env, &client), 3);
+    client.cancel_request(&3);
+    let events = last_events(&env);
+    assert!(events.contains("request_cancelled"), "{events}");
+
+    assert_eq!(submit_request(&env, &client), 4);
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    attest(&env, &client, 4, (&sk, &pk), &tee_hash);
+    let events = last_events(&env);
+    assert!(events.contains("request_expired"), "{events}");
+}